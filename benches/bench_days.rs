@@ -1,57 +1,136 @@
-use aoc2025::days;
+use aoc2025::input::Input;
+use aoc2025::{days, inputgen};
 use criterion::{criterion_group, criterion_main, Criterion};
-use std::fs;
 use std::hint::black_box;
 use std::path::PathBuf;
 
-// Takes a day number, reads the matching cached input file, and returns its lines for benchmarking.
-fn load_real_input(day: u32) -> Vec<String> {
+// Takes a day number, maps the matching cached input file, and returns it normalized for benchmarking.
+//
+// Without a cached input the day is benchmarked on seeded synthetic input instead; AOC_BENCH_SIZE
+// overrides its size.
+fn load_input(day: u32) -> Input {
     let path = PathBuf::from("input").join(format!("day{:02}.txt", day));
-    let data = fs::read_to_string(path).expect("Missing input file (run with --fetch first)");
-    data.lines().map(|s| s.to_string()).collect()
+    if let Ok(input) = Input::open(&path) {
+        return input.normalized();
+    }
+
+    let size = std::env::var("AOC_BENCH_SIZE")
+        .ok()
+        .and_then(|size| size.parse().ok())
+        .or_else(|| inputgen::default_size(day))
+        .expect("Missing input file (run with --fetch first)");
+    eprintln!(
+        "{} not found; using synthetic input (size {size}, seed {})",
+        path.display(),
+        inputgen::DEFAULT_SEED
+    );
+    let lines = inputgen::generate(day, size, inputgen::DEFAULT_SEED)
+        .expect("No input generator for this day");
+    Input::from_lines(&lines)
 }
 
-// Takes a Criterion runner and day, then benchmarks parsing, each part, and a full run for that solver.
+// Takes a Criterion runner and day, then benchmarks parsing, each declared part, and a full run for that solver.
 fn bench_day(c: &mut Criterion, day: u32) {
-    let lines = load_real_input(day);
+    let input = load_input(day);
 
-    c.bench_function(&format!("day{:02}_set_input", day), |b| {
+    // Loading the text as a `String` per line (the old `Vec<String>` interface) against one `Input` buffer.
+    let mut group = c.benchmark_group(format!("day{:02}_load", day));
+    group.bench_function("vec_string", |b| {
         b.iter(|| {
-            let mut d = days::make_solver(day).unwrap();
-            d.set_input(black_box(&lines));
+            let lines: Vec<String> = black_box(input.as_str())
+                .lines()
+                .map(str::to_string)
+                .collect();
+            black_box(lines);
         })
     });
-
-    c.bench_function(&format!("day{:02}_part1", day), |b| {
-        let mut d = days::make_solver(day).unwrap();
-        d.set_input(&lines);
+    group.bench_function("input", |b| {
         b.iter(|| {
-            black_box(d.part1());
+            let copy = Input::from(black_box(input.as_str()));
+            black_box(copy.line_count());
         })
     });
+    group.finish();
 
-    c.bench_function(&format!("day{:02}_part2", day), |b| {
-        let mut d = days::make_solver(day).unwrap();
-        d.set_input(&lines);
+    c.bench_function(&format!("day{:02}_set_input", day), |b| {
         b.iter(|| {
-            black_box(d.part2());
+            let mut d = days::make_solver(day).unwrap();
+            d.set_input(black_box(&input)).unwrap();
         })
     });
 
+    let parts = days::make_solver(day).unwrap().declared_parts();
+    for part in &parts {
+        c.bench_function(&format!("day{:02}_part{}", day, part.number), |b| {
+            let mut d = days::make_solver(day).unwrap();
+            d.set_input(&input).unwrap();
+            b.iter(|| {
+                black_box(d.solve_part(part.number));
+            })
+        });
+    }
+
     c.bench_function(&format!("day{:02}_full", day), |b| {
         b.iter(|| {
             let mut d = days::make_solver(day).unwrap();
-            d.set_input(&lines);
-            black_box(d.part1());
-            black_box(d.part2());
+            d.set_input(&input).unwrap();
+            for part in &parts {
+                black_box(d.solve_part(part.number));
+            }
         })
     });
 }
 
+// Takes a Criterion runner and day, then benchmarks every part that has variants with each of its implementations
+// side by side.
+//
+// Exhaustive references can be exponential, so the comparison runs on seeded synthetic input a tenth of the
+// real size; AOC_VARIANT_SIZE overrides it.
+fn bench_variants(c: &mut Criterion, day: u32) {
+    let solver = days::make_solver(day).unwrap();
+    let variants = solver.variants();
+    let Some(size) = std::env::var("AOC_VARIANT_SIZE")
+        .ok()
+        .and_then(|size| size.parse().ok())
+        .or_else(|| inputgen::default_size(day).map(|size| (size / 10).max(1)))
+    else {
+        return;
+    };
+    let Some(lines) = inputgen::generate(day, size, inputgen::DEFAULT_SEED) else {
+        return;
+    };
+    let input = Input::from_lines(&lines);
+
+    for part in solver.declared_parts() {
+        let names: Vec<&str> = variants
+            .iter()
+            .filter(|variant| variant.part == part.number)
+            .map(|variant| variant.name)
+            .collect();
+        if names.is_empty() {
+            continue;
+        }
+
+        let mut group = c.benchmark_group(format!("day{:02}_part{}_variants", day, part.number));
+        group.sample_size(10);
+        let mut d = days::make_solver(day).unwrap();
+        d.set_input(&input).unwrap();
+        for name in std::iter::once(days::DEFAULT_VARIANT).chain(names) {
+            group.bench_function(name, |b| {
+                b.iter(|| {
+                    black_box(d.solve_variant(part.number, name));
+                })
+            });
+        }
+        group.finish();
+    }
+}
+
 // Registers benchmarks for every implemented day and delegates per-day setup to bench_day.
 fn benches(c: &mut Criterion) {
     for day in days::implemented_days() {
         bench_day(c, day);
+        bench_variants(c, day);
     }
 }
 
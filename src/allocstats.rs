@@ -0,0 +1,166 @@
+use crate::benchmark::group_digits;
+use serde::{Deserialize, Serialize};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+// Opt-in allocation counting for the CLI and the `bench` command.
+//
+// Build with `--features count-allocs` to install `CountingAllocator` as the global allocator;
+// without it `measure` still runs the closure but reports nothing.
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+// The system allocator plus counters for allocations, allocated bytes and live bytes.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    // Takes a newly allocated size and records it.
+    fn record_alloc(size: usize) {
+        INSTALLED.store(true, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+            Self::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+// Allocation activity during one stage.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    // Allocations and reallocations made.
+    pub allocations: u64,
+    // Bytes requested by those allocations.
+    pub bytes: u64,
+    // Most bytes live at once, above what was already live when the stage started.
+    pub peak_bytes: u64,
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} B, peak {} B",
+            group_digits(self.allocations),
+            group_digits(self.bytes),
+            group_digits(self.peak_bytes)
+        )
+    }
+}
+
+// Allocation activity of each solver stage.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageAllocs {
+    pub set_input: AllocStats,
+    // Keyed by part number.
+    pub parts: BTreeMap<u8, AllocStats>,
+}
+
+// Returns whether the counting allocator is installed in this binary.
+pub fn is_active() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+// Takes a closure, runs it, and returns its result with the allocations it made (None without the counting allocator).
+//
+// Counters are process-wide, so allocations on other threads (e.g. rayon workers) are included.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !is_active() {
+        return (f(), None);
+    }
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+
+    let result = f();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(live) as u64,
+    };
+    (result, Some(stats))
+}
+
+// Lets `cargo test --features count-allocs` exercise the counters.
+#[cfg(all(test, feature = "count-allocs"))]
+#[global_allocator]
+static TEST_ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "count-allocs")]
+    #[test]
+    fn counts_allocations_and_peak() {
+        let (_, stats) = measure(|| {
+            let big = std::hint::black_box(vec![0u8; 100_000]);
+            drop(big);
+            std::hint::black_box(vec![0u8; 10]).len()
+        });
+        let stats = stats.unwrap();
+        assert!(stats.allocations >= 2);
+        assert!(stats.bytes >= 100_010);
+        assert!(stats.peak_bytes >= 100_000);
+    }
+
+    #[test]
+    fn reports_nothing_without_the_allocator() {
+        if is_active() {
+            return;
+        }
+        let (value, stats) = measure(|| vec![1u8; 64].len());
+        assert_eq!(value, 64);
+        assert_eq!(stats, None);
+    }
+
+    #[test]
+    fn displays_grouped_counts() {
+        let stats = AllocStats {
+            allocations: 4_501,
+            bytes: 1_234_567,
+            peak_bytes: 72,
+        };
+        assert_eq!(stats.to_string(), "4_501 allocs, 1_234_567 B, peak 72 B");
+    }
+}
@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayAnswers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+impl DayAnswers {
+    // Takes confirmed answers in page order and returns them assigned to part 1 and part 2.
+    pub fn from_confirmed(answers: &[String]) -> Self {
+        Self {
+            part1: answers.first().cloned(),
+            part2: answers.get(1).cloned(),
+        }
+    }
+
+    // Returns whether neither part has a stored answer.
+    pub fn is_empty(&self) -> bool {
+        self.part1.is_none() && self.part2.is_none()
+    }
+
+    // Takes a part number and returns its stored answer, if any.
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+// Local store of confirmed puzzle answers, kept next to the cached inputs.
+#[derive(Debug, Default)]
+pub struct AnswerStore {
+    path: PathBuf,
+    days: BTreeMap<u32, DayAnswers>,
+}
+
+impl AnswerStore {
+    // Returns the default store location inside the input cache directory.
+    pub fn default_path() -> PathBuf {
+        PathBuf::from("input").join("answers.yaml")
+    }
+
+    // Takes a store path, reads it if present, and returns the store (empty when the file is missing).
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let days = match fs::read_to_string(path) {
+            Ok(text) if text.trim().is_empty() => BTreeMap::new(),
+            Ok(text) => serde_yaml::from_str(&text).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Failed to parse {}: {e}", path.display()),
+                )
+            })?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            days,
+        })
+    }
+
+    // Writes the store back to its path, creating the parent directory if needed.
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let text = serde_yaml::to_string(&self.days)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(&self.path, text)
+    }
+
+    // Takes a day number and returns its stored answers, if any.
+    pub fn get(&self, day: u32) -> Option<&DayAnswers> {
+        self.days.get(&day)
+    }
+
+    // Takes a day and newly confirmed answers, merges them over stored ones, and returns whether anything changed.
+    pub fn record(&mut self, day: u32, answers: DayAnswers) -> bool {
+        let entry = self.days.entry(day).or_default();
+        let merged = DayAnswers {
+            part1: answers.part1.or_else(|| entry.part1.clone()),
+            part2: answers.part2.or_else(|| entry.part2.clone()),
+        };
+        let changed = *entry != merged;
+        *entry = merged;
+        changed
+    }
+
+    // Returns the stored days in ascending order with their answers.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &DayAnswers)> {
+        self.days.iter().map(|(&day, answers)| (day, answers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_keeps_existing_answers_and_reports_changes() {
+        let mut store = AnswerStore::default();
+        let first = DayAnswers::from_confirmed(&["42".to_string()]);
+        assert!(store.record(3, first.clone()));
+        assert!(!store.record(3, first));
+
+        let both = DayAnswers::from_confirmed(&["42".to_string(), "7".to_string()]);
+        assert!(store.record(3, both));
+        assert!(!store.record(3, DayAnswers::default()));
+        assert_eq!(store.get(3).unwrap().part(2), Some("7"));
+    }
+
+    #[test]
+    fn yaml_round_trip() {
+        let mut store = AnswerStore::default();
+        store.record(1, DayAnswers::from_confirmed(&["1".into(), "2".into()]));
+        store.record(12, DayAnswers::from_confirmed(&["3".into()]));
+
+        let text = serde_yaml::to_string(&store.days).unwrap();
+        let days: BTreeMap<u32, DayAnswers> = serde_yaml::from_str(&text).unwrap();
+        assert_eq!(days, store.days);
+    }
+}
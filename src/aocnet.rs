@@ -1,41 +1,729 @@
-use std::env;
-use std::error::Error;
-
-const YEAR: u32 = 2025;
-
-// Takes a day and AoC session cookie, fetches the input from adventofcode.com, and returns its lines.
-pub fn fetch_input(day: u32, session: &str) -> Result<Vec<String>, Box<dyn Error>> {
-    let url = format!("https://adventofcode.com/{}/day/{}/input", YEAR, day);
-
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .get(url)
-        .header(reqwest::header::COOKIE, format!("session={}", session))
-        .header(
-            reqwest::header::USER_AGENT,
-            format!("github.com/{}/aoc{} (Rust client)", get_username(), YEAR),
+use crate::input;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const YEAR: u32 = 2025;
+// Puzzles unlock at midnight EST (UTC-5), which is 05:00 UTC.
+const UNLOCK_HOUR_UTC: u64 = 5;
+const BASE_URL: &str = "https://adventofcode.com";
+const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(5);
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+const DEFAULT_RETRY_BASE: Duration = Duration::from_secs(1);
+// Upper bound for a single backoff sleep, including server-provided Retry-After values.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+// Size at which the request log is moved aside to `<log>.1` (replacing an older one) and started afresh.
+const REQUEST_LOG_LIMIT: u64 = 1 << 20;
+// How much of the request log's end is read to find its final entry; far longer than one entry.
+const REQUEST_LOG_TAIL: u64 = 4096;
+
+// Everything that can go wrong talking to adventofcode.com, split by what the user should do about it.
+#[derive(Debug)]
+pub enum Error {
+    // The session cookie is missing, expired or invalid (AoC asked to log in).
+    Unauthorized,
+    // The puzzle exists but has not unlocked yet.
+    NotYetUnlocked { remaining: Option<Duration> },
+    // The requested page does not exist (wrong year, day or leaderboard ID).
+    NotFound { path: String },
+    // AoC asked us to slow down.
+    RateLimited { retry_after: Option<Duration> },
+    // AoC answered with a 5xx status.
+    Server { status: u16 },
+    // Any other unexpected HTTP status.
+    Status { status: u16 },
+    // The request never produced a usable response (DNS, TLS, connection, timeout, body decoding).
+    Transport(reqwest::Error),
+}
+
+impl Error {
+    // Returns whether retrying the same request later may succeed.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Error::RateLimited { .. } | Error::Server { .. } | Error::Transport(_)
         )
-        .send()?;
+    }
+
+    // Returns a short, actionable suggestion for the CLI to print with this error.
+    pub fn advice(&self) -> &'static str {
+        match self {
+            Error::Unauthorized => "copy a fresh `session` cookie from your browser into the config file or AOC_SESSION, then run `doctor`",
+            Error::NotYetUnlocked { .. } => "wait for the unlock (see `countdown`, or run the day with --wait)",
+            Error::NotFound { .. } => "check the year, day or leaderboard ID",
+            Error::RateLimited { .. } => "you are requesting too often; raise AOC_MIN_INTERVAL_SECS and try again later",
+            Error::Server { .. } => "adventofcode.com is having trouble; try again in a few minutes",
+            Error::Status { .. } => "unexpected response; check the request log in input/requests.log",
+            Error::Transport(_) => "check your network connection (and AOC_BASE_URL if set)",
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Unauthorized => write!(f, "session expired or invalid (AoC asked to log in)"),
+            Error::NotYetUnlocked {
+                remaining: Some(remaining),
+            } => write!(f, "puzzle unlocks in {}", format_countdown(*remaining)),
+            Error::NotYetUnlocked { remaining: None } => write!(f, "puzzle is not unlocked yet"),
+            Error::NotFound { path } => write!(f, "{path} not found (status 404)"),
+            Error::RateLimited {
+                retry_after: Some(wait),
+            } => write!(f, "rate limited (retry after {}s)", wait.as_secs()),
+            Error::RateLimited { retry_after: None } => write!(f, "rate limited (status 429)"),
+            Error::Server { status } => write!(f, "server error (status {status})"),
+            Error::Status { status } => write!(f, "unexpected status {status}"),
+            Error::Transport(e) => write!(f, "request failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Transport(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Transport(e)
+    }
+}
+
+// Settings that control how politely the client talks to adventofcode.com.
+#[derive(Clone, Debug)]
+pub struct ClientOptions {
+    // Contact information for the User-Agent, e.g. "github.com/you/aoc2025 by you@example.com".
+    pub contact: Option<String>,
+    // Minimum time between two requests, enforced across runs through the request log.
+    pub min_interval: Duration,
+    // Append-only log of every request made, one line per request.
+    pub request_log: PathBuf,
+    // Site root; points at a local fixture server in tests.
+    pub base_url: String,
+    // Total tries for a request that fails transiently (rate limit, 5xx, transport).
+    pub max_attempts: u32,
+    // First backoff delay; doubles on every further retry.
+    pub retry_base: Duration,
+    // Event year the client requests pages for.
+    pub year: u32,
+    // Receives warnings and retry notices meant for the user; the library itself never prints. Ignores them by default.
+    pub notify: fn(&str),
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            contact: None,
+            min_interval: DEFAULT_MIN_INTERVAL,
+            request_log: PathBuf::from("input").join("requests.log"),
+            base_url: BASE_URL.to_string(),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            retry_base: DEFAULT_RETRY_BASE,
+            year: YEAR,
+            notify: |_| {},
+        }
+    }
+}
+
+impl ClientOptions {
+    // Applies AOC_CONTACT, AOC_MIN_INTERVAL_SECS and AOC_BASE_URL over these options and returns the result.
+    pub fn with_env(self) -> Self {
+        let mut options = self;
+        if let Ok(contact) = std::env::var("AOC_CONTACT") {
+            if !contact.trim().is_empty() {
+                options.contact = Some(contact.trim().to_string());
+            }
+        }
+        if let Some(secs) = std::env::var("AOC_MIN_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+        {
+            options.min_interval = Duration::from_secs(secs);
+        }
+        if let Ok(base_url) = std::env::var("AOC_BASE_URL") {
+            if !base_url.trim().is_empty() {
+                options.base_url = base_url.trim().trim_end_matches('/').to_string();
+            }
+        }
+        options
+    }
+}
+
+// Blocking AoC client that identifies itself, throttles and logs every request.
+pub struct Client {
+    http: reqwest::blocking::Client,
+    session: String,
+    user_agent: String,
+    options: ClientOptions,
+}
+
+impl Client {
+    // Takes a session cookie and client options and returns a client ready to make requests.
+    pub fn new(session: &str, options: ClientOptions) -> Self {
+        let year = options.year;
+        let user_agent = match &options.contact {
+            Some(contact) => format!("{contact} (aoc{year} Rust client)"),
+            None => {
+                (options.notify)(
+                    "Warning: no contact configured for the User-Agent (set AOC_CONTACT or `contact` in the config file).",
+                );
+                format!("aoc{year} Rust client (no contact configured)")
+            }
+        };
+
+        Self {
+            http: reqwest::blocking::Client::new(),
+            session: session.to_string(),
+            user_agent,
+            options,
+        }
+    }
+
+    // Returns the event year this client requests pages for.
+    pub fn year(&self) -> u32 {
+        self.options.year
+    }
+
+    // Takes a day, fetches its input from adventofcode.com, and returns its normalized lines (see `input::normalize`).
+    pub fn fetch_input(&self, day: u32) -> Result<Vec<String>, Error> {
+        let text = self.get_day(day, &format!("/{}/day/{day}/input", self.options.year))?;
+
+        Ok(input::normalize(&text)
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+
+    // Takes a day, fetches the day's puzzle page, and returns its HTML.
+    pub fn fetch_puzzle_page(&self, day: u32) -> Result<String, Error> {
+        self.get_day(day, &format!("/{}/day/{day}", self.options.year))
+    }
+
+    // Fetches the event page and returns the logged-in user's display name and star count.
+    pub fn whoami(&self) -> Result<UserInfo, Error> {
+        let html = self.get(&format!("/{}", self.options.year))?;
+        parse_user_info(&html).ok_or(Error::Unauthorized)
+    }
+
+    // Takes a private leaderboard ID, fetches its JSON view, and returns the raw JSON text.
+    pub fn fetch_leaderboard(&self, id: &str) -> Result<String, Error> {
+        let body = self.get(&format!(
+            "/{}/leaderboard/private/view/{id}.json",
+            self.options.year
+        ))?;
+        // Logged-out requests are redirected to an HTML login page rather than failing.
+        if body.trim_start().starts_with('<') {
+            return Err(Error::Unauthorized);
+        }
+        Ok(body)
+    }
+
+    // Fetches the personal leaderboard statistics page and returns its HTML.
+    pub fn fetch_personal_stats(&self) -> Result<String, Error> {
+        let html = self.get(&format!("/{}/leaderboard/self", self.options.year))?;
+        // Logged-out requests are redirected to the login page, which has no user header.
+        if parse_user_info(&html).is_none() {
+            return Err(Error::Unauthorized);
+        }
+        Ok(html)
+    }
+
+    // Takes a day and one of its paths, fetches it, and reports a 404 before unlock as NotYetUnlocked.
+    fn get_day(&self, day: u32, path: &str) -> Result<String, Error> {
+        match self.get(path) {
+            Err(Error::NotFound { .. }) => {
+                match time_until_unlock(self.options.year, day, SystemTime::now()) {
+                    Some(remaining) => Err(Error::NotYetUnlocked {
+                        remaining: Some(remaining),
+                    }),
+                    None => Err(Error::NotFound {
+                        path: path.to_string(),
+                    }),
+                }
+            }
+            other => other,
+        }
+    }
+
+    // Takes a site path, retries transient failures with exponential backoff, and returns the body.
+    fn get(&self, path: &str) -> Result<String, Error> {
+        let attempts = self.options.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            match self.request_once(path) {
+                Err(e) if e.is_transient() && attempt < attempts => {
+                    let retry_after = match &e {
+                        Error::RateLimited { retry_after } => *retry_after,
+                        _ => None,
+                    };
+                    let delay = backoff_delay(self.options.retry_base, attempt, retry_after);
+                    (self.options.notify)(&format!(
+                        "{path}: {e}; retrying in {:.1}s (attempt {}/{attempts})",
+                        delay.as_secs_f64(),
+                        attempt + 1
+                    ));
+                    thread::sleep(delay);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    // Takes a site path, waits for the throttle, performs one authenticated GET, logs it, and returns the body.
+    fn request_once(&self, path: &str) -> Result<String, Error> {
+        let last = last_request_time(&self.options.request_log);
+        let wait = throttle_delay(last, SystemTime::now(), self.options.min_interval);
+        if !wait.is_zero() {
+            thread::sleep(wait);
+        }
+
+        let url = format!("{}{path}", self.options.base_url);
+        let result = self
+            .http
+            .get(url)
+            .header(reqwest::header::COOKIE, format!("session={}", self.session))
+            .header(reqwest::header::USER_AGENT, &self.user_agent)
+            .send();
+
+        let status = match &result {
+            Ok(resp) => resp.status().as_u16().to_string(),
+            Err(_) => "error".to_string(),
+        };
+        if let Err(e) = append_request_log(&self.options.request_log, "GET", path, &status) {
+            (self.options.notify)(&format!("Warning: failed to write request log: {e}"));
+        }
+
+        let resp = result?;
+        let status = resp.status().as_u16();
+        let retry_after = resp
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(Duration::from_secs);
+        let body = resp.text()?;
+
+        match classify_status(status, &body, retry_after) {
+            Some(e) => Err(match e {
+                Error::NotFound { .. } => Error::NotFound {
+                    path: path.to_string(),
+                },
+                e => e,
+            }),
+            None => Ok(body),
+        }
+    }
+}
+
+// Takes a response status, body and Retry-After value, and returns the matching error for non-success responses.
+fn classify_status(status: u16, body: &str, retry_after: Option<Duration>) -> Option<Error> {
+    match status {
+        200..=299 => None,
+        404 if body.contains("before it unlocks") => {
+            Some(Error::NotYetUnlocked { remaining: None })
+        }
+        404 => Some(Error::NotFound {
+            path: String::new(),
+        }),
+        401 | 403 => Some(Error::Unauthorized),
+        400 if asks_to_log_in(body) => Some(Error::Unauthorized),
+        429 => Some(Error::RateLimited { retry_after }),
+        500..=599 => Some(Error::Server { status }),
+        _ => Some(Error::Status { status }),
+    }
+}
+
+// Takes the base delay, the number of attempts so far and any Retry-After hint, and returns the capped wait.
+fn backoff_delay(base: Duration, attempt: u32, retry_after: Option<Duration>) -> Duration {
+    let exponential = base.saturating_mul(1u32 << (attempt - 1).min(16));
+    retry_after
+        .unwrap_or(Duration::ZERO)
+        .max(exponential)
+        .min(MAX_BACKOFF)
+}
+
+// Takes an event year and returns how many puzzles it has (twelve from 2025 on, 25 before).
+pub fn puzzle_count(year: u32) -> u32 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+// Takes an event year and day and returns the moment its puzzle unlocks (midnight EST on December <day>).
+pub fn unlock_time(year: u32, day: u32) -> SystemTime {
+    let days = days_from_civil(i64::from(year), 12, i64::from(day));
+    UNIX_EPOCH + Duration::from_secs(days as u64 * 86_400 + UNLOCK_HOUR_UTC * 3_600)
+}
+
+// Takes an event year, day and the current time, and returns the remaining wait if the puzzle is still locked.
+pub fn time_until_unlock(year: u32, day: u32, now: SystemTime) -> Option<Duration> {
+    unlock_time(year, day)
+        .duration_since(now)
+        .ok()
+        .filter(|remaining| !remaining.is_zero())
+}
+
+// Takes the current time and returns the most recently unlocked puzzle day while the event is running.
+pub fn current_puzzle_day(year: u32, now: SystemTime) -> Option<u32> {
+    let last = puzzle_count(year);
+    if time_until_unlock(year, 1, now).is_some() || time_until_unlock(year, last + 1, now).is_none()
+    {
+        return None;
+    }
+    (1..=last)
+        .rev()
+        .find(|&day| time_until_unlock(year, day, now).is_none())
+}
+
+// Takes a duration and returns it as "[Nd ]HH:MM:SS" for countdown displays.
+pub fn format_countdown(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    let (days, hours, minutes, seconds) =
+        (secs / 86_400, secs / 3_600 % 24, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+// Takes a proleptic Gregorian date and returns its day number relative to 1970-01-01.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// The account a session cookie belongs to, as shown in the site header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UserInfo {
+    pub name: String,
+    pub stars: Option<u32>,
+}
+
+// Takes any AoC page HTML and returns the logged-in user from its header, or None if logged out.
+pub fn parse_user_info(html: &str) -> Option<UserInfo> {
+    const USER_DIV: &str = r#"<div class="user">"#;
+    const STAR_SPAN: &str = r#"<span class="star-count">"#;
+
+    let start = html.find(USER_DIV)? + USER_DIV.len();
+    let div = &html[start..];
+    let div = &div[..div.find("</div>").unwrap_or(div.len())];
+
+    let name = decode_entities(div[..div.find('<').unwrap_or(div.len())].trim());
+    let stars = div.find(STAR_SPAN).and_then(|pos| {
+        let count = &div[pos + STAR_SPAN.len()..];
+        count[..count.find('*')?].trim().parse().ok()
+    });
 
-    let status = resp.status();
-    if !status.is_success() {
-        return Err(format!("failed to fetch input: status {}", status.as_u16()).into());
+    Some(UserInfo { name, stars })
+}
+
+// Takes a response body and returns whether it is AoC's "please log in" message.
+fn asks_to_log_in(body: &str) -> bool {
+    body.to_ascii_lowercase().contains("log in")
+}
+
+// Takes puzzle page HTML and returns every "Your puzzle answer was ..." value in page order.
+pub fn parse_confirmed_answers(html: &str) -> Vec<String> {
+    const MARKER: &str = "Your puzzle answer was";
+
+    let mut answers = Vec::new();
+    let mut rest = html;
+    while let Some(pos) = rest.find(MARKER) {
+        rest = &rest[pos + MARKER.len()..];
+        let Some(open) = rest.find("<code>") else {
+            break;
+        };
+        let Some(close) = rest[open..].find("</code>") else {
+            break;
+        };
+        let value = &rest[open + "<code>".len()..open + close];
+        answers.push(decode_entities(value.trim()));
+        rest = &rest[open + close..];
     }
+    answers
+}
+
+// Takes an HTML text fragment, replaces the basic character entities, and returns plain text.
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
 
-    let text = resp.text()?;
+// Takes the previous request time, the current time and the minimum interval, and returns how long to wait.
+fn throttle_delay(last: Option<SystemTime>, now: SystemTime, min_interval: Duration) -> Duration {
+    let Some(last) = last else {
+        return Duration::ZERO;
+    };
+    let elapsed = now.duration_since(last).unwrap_or(Duration::ZERO);
+    min_interval.saturating_sub(elapsed)
+}
+
+// Takes the request log path, reads only its final entry, and returns when that request was made.
+fn last_request_time(log: &Path) -> Option<SystemTime> {
+    let mut f = fs::File::open(log).ok()?;
+    let len = f.metadata().ok()?.len();
+    f.seek(SeekFrom::Start(len.saturating_sub(REQUEST_LOG_TAIL)))
+        .ok()?;
+    let mut tail = Vec::new();
+    f.read_to_end(&mut tail).ok()?;
+    let tail = String::from_utf8_lossy(&tail);
+    let millis: u64 = tail.lines().last()?.split('\t').next()?.parse().ok()?;
+    Some(UNIX_EPOCH + Duration::from_millis(millis))
+}
 
-    // Behaves like your Go: splits on '\n', trims trailing '\r', keeps empty lines if they exist
-    let lines = text
-        .split_terminator('\n')
-        .map(|l| l.trim_end_matches('\r').to_string())
-        .collect::<Vec<_>>();
+// Takes the request log path and a size limit, and moves the log to `<log>.1` once it has grown past the limit.
+fn rotate_request_log(log: &Path, limit: u64) -> std::io::Result<()> {
+    match fs::metadata(log) {
+        Ok(metadata) if metadata.len() >= limit => {
+            let mut rotated = log.as_os_str().to_os_string();
+            rotated.push(".1");
+            fs::rename(log, rotated)
+        }
+        _ => Ok(()),
+    }
+}
 
-    Ok(lines)
+// Takes a request description, appends it to the log with the current time, and returns any I/O error.
+fn append_request_log(log: &Path, method: &str, path: &str, status: &str) -> std::io::Result<()> {
+    if let Some(parent) = log.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_millis();
+    rotate_request_log(log, REQUEST_LOG_LIMIT)?;
+    let mut f = OpenOptions::new().create(true).append(true).open(log)?;
+    writeln!(f, "{millis}\t{method}\t{path}\t{status}")
 }
 
-// Reads the local OS username for the User-Agent header and falls back to "anonymous".
-fn get_username() -> String {
-    env::var("USER")
-        .or_else(|_| env::var("USERNAME"))
-        .unwrap_or_else(|_| "anonymous".to_string())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOLVED_PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Secret Entrance ---</h2><p>...</p></article>
+<p>Your puzzle answer was <code>1152</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>...</p></article>
+<p>Your puzzle answer was <code>6671</code>.</p>
+<p class="day-success">Both parts of this puzzle are complete! They provide two gold stars: **</p>
+</main>"#;
+
+    #[test]
+    fn parses_both_confirmed_answers() {
+        assert_eq!(parse_confirmed_answers(SOLVED_PAGE), vec!["1152", "6671"]);
+    }
+
+    #[test]
+    fn unsolved_page_has_no_answers() {
+        let html = r#"<article class="day-desc"><p>...</p></article>
+<form method="post" action="1/answer"><input type="text" name="answer"/></form>"#;
+        assert!(parse_confirmed_answers(html).is_empty());
+    }
+
+    #[test]
+    fn parses_logged_in_user_from_header() {
+        let html = r#"<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1>
+<div class="user">Jane Doe <span class="star-count">24*</span></div></div></header>"#;
+        assert_eq!(
+            parse_user_info(html),
+            Some(UserInfo {
+                name: "Jane Doe".to_string(),
+                stars: Some(24)
+            })
+        );
+    }
+
+    #[test]
+    fn logged_out_page_has_no_user() {
+        let html = r#"<div><a href="/2025/auth/login">[Log In]</a></div>"#;
+        assert_eq!(parse_user_info(html), None);
+        assert!(asks_to_log_in(
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input."
+        ));
+    }
+
+    #[test]
+    fn puzzles_unlock_at_midnight_eastern() {
+        let secs = |t: SystemTime| t.duration_since(UNIX_EPOCH).unwrap().as_secs();
+        assert_eq!(secs(unlock_time(2025, 1)), 1_764_565_200);
+        assert_eq!(secs(unlock_time(2025, 12)), 1_765_515_600);
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+    }
+
+    #[test]
+    fn current_day_only_during_the_event() {
+        let day1 = unlock_time(2025, 1);
+        assert_eq!(
+            current_puzzle_day(2025, day1 - Duration::from_secs(1)),
+            None
+        );
+        assert_eq!(current_puzzle_day(2025, day1), Some(1));
+        assert_eq!(
+            current_puzzle_day(2025, unlock_time(2025, 7) + Duration::from_secs(3_600)),
+            Some(7)
+        );
+        assert_eq!(current_puzzle_day(2025, unlock_time(2025, 13)), None);
+        assert_eq!(
+            time_until_unlock(2025, 2, day1),
+            Some(Duration::from_secs(86_400))
+        );
+        assert_eq!(time_until_unlock(2025, 1, day1), None);
+    }
+
+    #[test]
+    fn countdown_formatting() {
+        assert_eq!(format_countdown(Duration::from_secs(59)), "00:00:59");
+        assert_eq!(format_countdown(Duration::from_secs(3_661)), "01:01:01");
+        assert_eq!(
+            format_countdown(Duration::from_secs(2 * 86_400 + 5)),
+            "2d 00:00:05"
+        );
+    }
+
+    #[test]
+    fn statuses_map_to_error_kinds() {
+        let kind = |status, body| classify_status(status, body, None);
+        assert!(kind(200, "").is_none());
+        assert!(matches!(
+            kind(
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input."
+            ),
+            Some(Error::Unauthorized)
+        ));
+        assert!(matches!(
+            kind(400, "Bad request"),
+            Some(Error::Status { status: 400 })
+        ));
+        assert!(matches!(
+            kind(
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!"
+            ),
+            Some(Error::NotYetUnlocked { .. })
+        ));
+        assert!(matches!(
+            kind(404, "404 Not Found"),
+            Some(Error::NotFound { .. })
+        ));
+        assert!(matches!(kind(429, ""), Some(Error::RateLimited { .. })));
+        assert!(matches!(kind(502, ""), Some(Error::Server { status: 502 })));
+        assert!(kind(503, "").unwrap().is_transient());
+        assert!(!kind(404, "").unwrap().is_transient());
+    }
+
+    #[test]
+    fn backoff_doubles_and_honours_retry_after() {
+        let base = Duration::from_secs(1);
+        assert_eq!(backoff_delay(base, 1, None), Duration::from_secs(1));
+        assert_eq!(backoff_delay(base, 2, None), Duration::from_secs(2));
+        assert_eq!(backoff_delay(base, 3, None), Duration::from_secs(4));
+        assert_eq!(
+            backoff_delay(base, 1, Some(Duration::from_secs(30))),
+            Duration::from_secs(30)
+        );
+        assert_eq!(backoff_delay(base, 30, None), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn throttle_waits_out_the_remaining_interval() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+        let interval = Duration::from_secs(5);
+        assert_eq!(throttle_delay(None, now, interval), Duration::ZERO);
+        assert_eq!(
+            throttle_delay(Some(now - Duration::from_secs(2)), now, interval),
+            Duration::from_secs(3)
+        );
+        assert_eq!(
+            throttle_delay(Some(now - Duration::from_secs(9)), now, interval),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn request_log_records_last_request_time() {
+        let log = std::env::temp_dir().join(format!("aoc2025-log-{}.log", std::process::id()));
+        let _ = fs::remove_file(&log);
+        assert!(last_request_time(&log).is_none());
+
+        let before = SystemTime::now() - Duration::from_secs(1);
+        append_request_log(&log, "GET", "/2025/day/1/input", "200").unwrap();
+        append_request_log(&log, "GET", "/2025/day/2/input", "404").unwrap();
+        let last = last_request_time(&log).unwrap();
+        assert!(last >= before && last <= SystemTime::now());
+        assert_eq!(fs::read_to_string(&log).unwrap().lines().count(), 2);
+
+        let rotated = log.with_extension("log.1");
+        rotate_request_log(&log, 1 << 20).unwrap();
+        assert!(!rotated.exists());
+        rotate_request_log(&log, 10).unwrap();
+        assert!(!log.exists());
+        assert_eq!(last_request_time(&rotated), Some(last));
+
+        fs::write(&log, "1\tGET\t/\t200\n".repeat(1000)).unwrap();
+        append_request_log(&log, "GET", "/2025/day/3/input", "200").unwrap();
+        assert!(last_request_time(&log).unwrap() >= last);
+
+        fs::remove_file(&log).unwrap();
+        fs::remove_file(&rotated).unwrap();
+    }
+
+    #[test]
+    fn user_agent_names_the_client_year_and_reports_a_missing_contact() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static NOTICES: AtomicUsize = AtomicUsize::new(0);
+
+        let options = ClientOptions {
+            year: 2017,
+            notify: |message| {
+                assert!(message.contains("no contact configured"), "{message}");
+                NOTICES.fetch_add(1, Ordering::SeqCst);
+            },
+            ..ClientOptions::default()
+        };
+        let client = Client::new("session", options.clone());
+        assert_eq!(
+            client.user_agent,
+            "aoc2017 Rust client (no contact configured)"
+        );
+        assert_eq!(NOTICES.load(Ordering::SeqCst), 1);
+
+        let client = Client::new(
+            "session",
+            ClientOptions {
+                contact: Some("me".to_string()),
+                ..options
+            },
+        );
+        assert_eq!(client.user_agent, "me (aoc2017 Rust client)");
+        assert_eq!(NOTICES.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn decodes_entities_in_answers() {
+        let html = "<p>Your puzzle answer was <code>a&lt;b&amp;c</code>.</p>";
+        assert_eq!(parse_confirmed_answers(html), vec!["a<b&c"]);
+    }
 }
@@ -0,0 +1,525 @@
+use crate::allocstats::{self, StageAllocs};
+use crate::input::Input;
+use crate::{cache, days, inputgen, parallel};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// Heading that starts the README's benchmark table section.
+const README_HEADING: &str = "## 📊 Benchmark Summary";
+
+// Median wall-clock time of each stage for one day, in microseconds.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DayTiming {
+    pub set_input_us: f64,
+    // Keyed by part number; a day has a time for each part it declares.
+    pub parts_us: BTreeMap<u8, f64>,
+    pub full_us: f64,
+    pub samples: usize,
+    // Whether the day ran on generated input because no cached input existed.
+    #[serde(default)]
+    pub synthetic: bool,
+    // Allocations per stage, recorded when built with the count-allocs feature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocations: Option<StageAllocs>,
+}
+
+// The machine a baseline was recorded on.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MachineInfo {
+    pub cpu: String,
+    pub os: String,
+    pub arch: String,
+    // Threads the parallel solvers ran on (see `--threads`).
+    pub threads: usize,
+    pub rustc: String,
+}
+
+impl MachineInfo {
+    // Describes the current machine and toolchain.
+    pub fn current() -> Self {
+        Self {
+            cpu: cpu_model().unwrap_or_else(|| "unknown CPU".to_string()),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            threads: parallel::current_threads(),
+            rustc: command_output("rustc", &["--version"])
+                .unwrap_or_else(|| "rustc (unknown version)".to_string()),
+        }
+    }
+}
+
+// A named set of timings saved under baselines/<name>.json.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub name: String,
+    pub recorded_at: u64,
+    pub machine: MachineInfo,
+    pub days: BTreeMap<u32, DayTiming>,
+}
+
+impl Baseline {
+    // Takes a baseline name and returns where it is stored.
+    pub fn path(name: &str) -> PathBuf {
+        PathBuf::from("baselines").join(format!("{name}.json"))
+    }
+
+    // Takes a baseline name and returns the stored baseline.
+    pub fn load(name: &str) -> io::Result<Self> {
+        let path = Self::path(name);
+        let text = fs::read_to_string(&path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("Failed to read baseline {}: {e}", path.display()),
+            )
+        })?;
+        serde_json::from_str(&text).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse {}: {e}", path.display()),
+            )
+        })
+    }
+
+    // Writes the baseline to baselines/<name>.json and returns the path.
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let path = Self::path(&self.name);
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        cache::write_atomic(&path, json.as_bytes())?;
+        Ok(path)
+    }
+}
+
+// How one day's full-pipeline time moved between two baselines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub day: u32,
+    pub before_us: f64,
+    pub after_us: f64,
+    // Relative change in percent; positive means slower.
+    pub percent: f64,
+    pub regression: bool,
+}
+
+// Takes a day number and returns its cached input, normalized, or seeded synthetic input when none is cached.
+pub fn load_input(day: u32) -> Option<(Input, bool)> {
+    if let Ok(input) = Input::open(&cache::input_path(day)) {
+        return Some((input.normalized(), false));
+    }
+    let size = inputgen::default_size(day)?;
+    let lines = inputgen::generate(day, size, inputgen::DEFAULT_SEED)?;
+    Some((Input::from_lines(&lines), true))
+}
+
+// Takes a day, its input and a time budget, runs the solver repeatedly, and returns median stage timings.
+pub fn measure_day(day: u32, input: &Input, budget: Duration) -> Option<DayTiming> {
+    const MIN_SAMPLES: usize = 5;
+    const MAX_SAMPLES: usize = 10_000;
+
+    // One untimed run warms caches and the rayon pool, and counts allocations if enabled.
+    let mut solver = days::make_solver(day)?;
+    let parts: Vec<u8> = solver
+        .declared_parts()
+        .iter()
+        .map(|part| part.number)
+        .collect();
+    let (parsed, set_input) = allocstats::measure(|| solver.set_input(input));
+    parsed.unwrap_or_else(|e| panic!("malformed puzzle input: {e}"));
+    let part_allocs: Option<BTreeMap<u8, _>> = parts
+        .iter()
+        .map(|&part| {
+            allocstats::measure(|| solver.solve_part(part))
+                .1
+                .map(|a| (part, a))
+        })
+        .collect();
+    let allocations = set_input
+        .zip(part_allocs)
+        .map(|(set_input, parts)| StageAllocs { set_input, parts });
+
+    // Samples for set_input, then each declared part, then the full pipeline.
+    let mut stages: Vec<Vec<f64>> = vec![Vec::new(); parts.len() + 2];
+    let started = Instant::now();
+    while stages[0].len() < MIN_SAMPLES
+        || (started.elapsed() < budget && stages[0].len() < MAX_SAMPLES)
+    {
+        let mut solver = days::make_solver(day)?;
+        let mut marks = vec![Instant::now()];
+        solver.set_input(input).unwrap();
+        marks.push(Instant::now());
+        for &part in &parts {
+            std::hint::black_box(solver.solve_part(part));
+            marks.push(Instant::now());
+        }
+
+        for (samples, window) in stages.iter_mut().zip(marks.windows(2)) {
+            samples.push((window[1] - window[0]).as_secs_f64() * 1e6);
+        }
+        let full = marks[marks.len() - 1] - marks[0];
+        stages[parts.len() + 1].push(full.as_secs_f64() * 1e6);
+    }
+
+    let samples = stages[0].len();
+    let mut medians = stages.into_iter().map(median);
+    let set_input = medians.next().unwrap_or_default();
+    let parts_us = parts.iter().copied().zip(medians.by_ref()).collect();
+    Some(DayTiming {
+        set_input_us: set_input,
+        parts_us,
+        full_us: medians.next().unwrap_or_default(),
+        samples,
+        synthetic: false,
+        allocations,
+    })
+}
+
+// One size of a scaling sweep.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScalingPoint {
+    pub size: usize,
+    // Length of the generated input in bytes, newlines included.
+    pub bytes: usize,
+    pub timing: DayTiming,
+}
+
+// Takes a smallest and largest size and a growth factor and returns the geometric series between them.
+pub fn geometric_sizes(from: usize, to: usize, factor: f64) -> Vec<usize> {
+    let mut sizes = Vec::new();
+    let mut size = from.max(1) as f64;
+    while size.round() as usize <= to {
+        let rounded = size.round() as usize;
+        if sizes.last() != Some(&rounded) {
+            sizes.push(rounded);
+        }
+        size *= factor.max(1.01);
+    }
+    sizes
+}
+
+// Takes a day, sizes and a time budget per size, times the solver on generated input of each size, and returns the points.
+pub fn sweep(
+    day: u32,
+    sizes: &[usize],
+    budget: Duration,
+    mut progress: impl FnMut(&ScalingPoint),
+) -> Vec<ScalingPoint> {
+    let mut points = Vec::new();
+    for &size in sizes {
+        let Some(lines) = inputgen::generate(day, size, inputgen::DEFAULT_SEED) else {
+            break;
+        };
+        let input = Input::from_lines(&lines);
+        let Some(mut timing) = measure_day(day, &input, budget) else {
+            break;
+        };
+        timing.synthetic = true;
+        let point = ScalingPoint {
+            size,
+            bytes: input.len(),
+            timing,
+        };
+        progress(&point);
+        points.push(point);
+    }
+    points
+}
+
+// Takes (size, time) pairs and returns the least-squares slope of log(time) over log(size), i.e. k in time ∝ size^k.
+pub fn fit_exponent(points: &[(f64, f64)]) -> Option<f64> {
+    let logs: Vec<(f64, f64)> = points
+        .iter()
+        .filter(|&&(size, time)| size > 0.0 && time > 0.0)
+        .map(|&(size, time)| (size.ln(), time.ln()))
+        .collect();
+    if logs.len() < 2 {
+        return None;
+    }
+
+    let n = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = logs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+// Takes a day and sweep points and returns them as CSV with a header row and a column per declared part.
+pub fn scaling_csv(day: u32, points: &[ScalingPoint]) -> String {
+    let parts: Vec<u8> = points
+        .first()
+        .map(|point| point.timing.parts_us.keys().copied().collect())
+        .unwrap_or_default();
+    let mut out = String::from("day,size,bytes,set_input_us");
+    for part in &parts {
+        let _ = write!(out, ",part{part}_us");
+    }
+    out.push_str(",full_us\n");
+    for point in points {
+        let t = &point.timing;
+        let _ = write!(
+            out,
+            "{day},{},{},{:.3}",
+            point.size, point.bytes, t.set_input_us
+        );
+        for part in &parts {
+            let _ = write!(out, ",{:.3}", t.parts_us.get(part).copied().unwrap_or(0.0));
+        }
+        let _ = writeln!(out, ",{:.3}", t.full_us);
+    }
+    out
+}
+
+// Takes samples and returns their median (0 when empty).
+fn median(mut samples: Vec<f64>) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    samples.sort_by(f64::total_cmp);
+    let mid = samples.len() / 2;
+    if samples.len().is_multiple_of(2) {
+        (samples[mid - 1] + samples[mid]) / 2.0
+    } else {
+        samples[mid]
+    }
+}
+
+// Takes two baselines and a threshold in percent and returns the full-pipeline change for every day in both.
+pub fn compare(before: &Baseline, after: &Baseline, threshold: f64) -> Vec<Change> {
+    after
+        .days
+        .iter()
+        .filter_map(|(&day, timing)| {
+            let old = before.days.get(&day)?;
+            let percent = if old.full_us > 0.0 {
+                (timing.full_us - old.full_us) / old.full_us * 100.0
+            } else {
+                0.0
+            };
+            Some(Change {
+                day,
+                before_us: old.full_us,
+                after_us: timing.full_us,
+                percent,
+                regression: percent > threshold,
+            })
+        })
+        .collect()
+}
+
+// Takes a time in microseconds and returns it with two decimals and `_` thousands separators (3_134.70).
+pub fn format_micros(us: f64) -> String {
+    let text = format!("{:.2}", us.max(0.0));
+    let (whole, fraction) = text.split_once('.').unwrap_or((&text, "00"));
+    format!("{}.{fraction}", group_digits(whole.parse().unwrap_or(0)))
+}
+
+// Takes a count and returns it with `_` thousands separators.
+pub fn group_digits(value: u64) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push('_');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+// Takes a baseline and returns the README benchmark section for it, heading included.
+pub fn readme_section(baseline: &Baseline) -> String {
+    let machine = &baseline.machine;
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{README_HEADING} — {} ({}/{})",
+        machine.cpu, machine.os, machine.arch
+    );
+    out.push('\n');
+    let _ = writeln!(
+        out,
+        "Measured with {} using the built-in timer (`cargo run --release -- bench`, baseline `{}`, median per day).",
+        machine.rustc, baseline.name
+    );
+    if baseline.days.values().any(|timing| timing.synthetic) {
+        out.push_str("Days marked † ran on synthetic input.\n");
+    }
+    out.push('\n');
+    out.push_str("| Day | Full pipeline (µs) |\n");
+    out.push_str("| --- | ------------------ |\n");
+    for (day, timing) in &baseline.days {
+        let mut time = format_micros(timing.full_us);
+        if timing.synthetic {
+            time.push_str(" †");
+        }
+        let _ = writeln!(out, "| {:<3} | {:<18} |", format!("{day:02}"), time);
+    }
+    out
+}
+
+// Takes the README text and a new benchmark section and returns the README with the old section replaced.
+//
+// The section runs from its heading up to the next `---` rule; it is appended if the README has none.
+pub fn replace_readme_section(readme: &str, section: &str) -> String {
+    let Some(start) = readme.find(README_HEADING) else {
+        return format!("{}\n\n---\n\n{section}", readme.trim_end());
+    };
+    let end = readme[start..]
+        .find("\n---")
+        .map_or(readme.len(), |offset| start + offset + 1);
+    format!("{}{section}\n{}", &readme[..start], &readme[end..])
+}
+
+// Returns the current time as Unix seconds.
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+// Returns the CPU model name from /proc/cpuinfo or sysctl, if the platform exposes it.
+fn cpu_model() -> Option<String> {
+    if let Ok(info) = fs::read_to_string("/proc/cpuinfo") {
+        let model = info
+            .lines()
+            .find(|line| line.starts_with("model name") || line.starts_with("Model"))
+            .and_then(|line| line.split_once(':'))
+            .map(|(_, model)| model.trim().to_string());
+        if model.is_some() {
+            return model;
+        }
+    }
+    command_output("sysctl", &["-n", "machdep.cpu.brand_string"])
+}
+
+// Takes a program and arguments and returns its trimmed stdout if it ran successfully.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+// Takes a baseline path's directory and returns the names of every saved baseline.
+pub fn saved_baselines(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.strip_suffix(".json").map(str::to_string)
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn baseline(name: &str, times: &[(u32, f64)]) -> Baseline {
+        Baseline {
+            name: name.to_string(),
+            machine: MachineInfo {
+                cpu: "Test CPU".to_string(),
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                threads: 8,
+                rustc: "rustc 1.95.0".to_string(),
+            },
+            days: times
+                .iter()
+                .map(|&(day, full_us)| {
+                    (
+                        day,
+                        DayTiming {
+                            full_us,
+                            ..DayTiming::default()
+                        },
+                    )
+                })
+                .collect(),
+            ..Baseline::default()
+        }
+    }
+
+    #[test]
+    fn flags_regressions_beyond_threshold() {
+        let before = baseline("old", &[(1, 100.0), (2, 100.0), (3, 100.0)]);
+        let after = baseline("new", &[(1, 109.0), (2, 125.0), (4, 1.0)]);
+        let changes = compare(&before, &after, 10.0);
+        assert_eq!(changes.len(), 2);
+        assert!(!changes[0].regression);
+        assert!(changes[1].regression);
+        assert!((changes[1].percent - 25.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn formats_micros_with_separators() {
+        assert_eq!(format_micros(44.519), "44.52");
+        assert_eq!(format_micros(3134.7), "3_134.70");
+        assert_eq!(format_micros(1234567.0), "1_234_567.00");
+    }
+
+    #[test]
+    fn replaces_only_the_benchmark_section() {
+        let readme = "# Title\n\n---\n\n## 📊 Benchmark Summary — Old\n\n| Day |\n\n---\n\n## ✅ Design Goals\n";
+        let section = readme_section(&baseline("m4", &[(1, 44.52), (8, 3134.7)]));
+        let updated = replace_readme_section(readme, &section);
+
+        assert!(updated
+            .starts_with("# Title\n\n---\n\n## 📊 Benchmark Summary — Test CPU (linux/x86_64)\n"));
+        assert!(updated.contains("| 08  | 3_134.70           |\n"));
+        assert!(updated.contains("baseline `m4`"));
+        assert!(!updated.contains("— Old"));
+        assert!(updated.ends_with("|\n\n---\n\n## ✅ Design Goals\n"));
+    }
+
+    #[test]
+    fn fits_growth_exponent() {
+        let quadratic: Vec<(f64, f64)> = [10.0, 20.0, 40.0, 80.0]
+            .iter()
+            .map(|&n| (n, 3.0 * n * n))
+            .collect();
+        assert!((fit_exponent(&quadratic).unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(fit_exponent(&quadratic[..1]), None);
+    }
+
+    #[test]
+    fn sweeps_a_geometric_series() {
+        assert_eq!(geometric_sizes(10, 100, 2.0), vec![10, 20, 40, 80]);
+        assert_eq!(geometric_sizes(1, 3, 1.5), vec![1, 2, 3]);
+
+        let points = sweep(1, &[8, 16], Duration::ZERO, |_| {});
+        assert_eq!(points.len(), 2);
+        let csv = scaling_csv(1, &points);
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            rows[0],
+            "day,size,bytes,set_input_us,part1_us,part2_us,full_us"
+        );
+        assert!(rows[2].starts_with("1,16,"));
+    }
+
+    #[test]
+    fn measures_every_stage() {
+        let (input, _) = load_input(1).unwrap();
+        let timing = measure_day(1, &input, Duration::ZERO).unwrap();
+        assert_eq!(timing.samples, 5);
+        assert!(timing.full_us >= timing.parts_us[&1]);
+        assert_eq!(timing.parts_us.len(), 2);
+
+        let (input, _) = load_input(12).unwrap();
+        let timing = measure_day(12, &input, Duration::ZERO).unwrap();
+        assert_eq!(timing.parts_us.keys().collect::<Vec<_>>(), vec![&1]);
+    }
+}
@@ -0,0 +1,78 @@
+use aoc2025::fakeaoc::{Failure, FakeAoc, Fixtures};
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Local fake of adventofcode.com for offline testing (point AOC_BASE_URL at it)
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+struct Args {
+    /// Directory laid out like input/ (dayNN.txt, dayNN.html, leaderboard-<id>.json, answers.yaml)
+    #[arg(long, default_value = "input")]
+    fixtures: PathBuf,
+
+    /// Port to listen on (0 picks a free one)
+    #[arg(long, default_value_t = 0)]
+    port: u16,
+
+    /// Event year to serve
+    #[arg(long)]
+    year: Option<u32>,
+
+    /// Session cookie accepted as logged in
+    #[arg(long)]
+    session: Option<String>,
+
+    /// Display name shown in the page header
+    #[arg(long)]
+    user: Option<String>,
+
+    /// Days that answer as not unlocked yet (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    locked: Vec<u32>,
+
+    /// Scripted failure as PATH=STATUS[:TIMES], e.g. /2025/day/3/input=503:2 (repeatable)
+    #[arg(long = "fail", value_parser = Failure::parse)]
+    failures: Vec<Failure>,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let mut fixtures = match Fixtures::from_dir(&args.fixtures) {
+        Ok(fixtures) => fixtures,
+        Err(e) => {
+            eprintln!("Failed to load fixtures from {:?}: {e}", args.fixtures);
+            std::process::exit(1);
+        }
+    };
+    if let Some(year) = args.year {
+        fixtures.year = year;
+    }
+    if let Some(session) = args.session {
+        fixtures.session = session;
+    }
+    if let Some(user) = args.user {
+        fixtures.user = user;
+    }
+    fixtures.locked.extend(args.locked);
+
+    let session = fixtures.session.clone();
+    let days = fixtures.inputs.len();
+    let site = match FakeAoc::bind(("127.0.0.1", args.port), fixtures) {
+        Ok(site) => site,
+        Err(e) => {
+            eprintln!("Failed to listen on port {}: {e}", args.port);
+            std::process::exit(1);
+        }
+    };
+    for failure in args.failures {
+        site.fail(failure);
+    }
+
+    println!("Fake AoC serving {days} input(s) at {}", site.base_url());
+    println!(
+        "Use it with: AOC_BASE_URL={} AOC_SESSION={session} AOC_MIN_INTERVAL_SECS=0",
+        site.base_url()
+    );
+    site.wait();
+}
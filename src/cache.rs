@@ -0,0 +1,399 @@
+use crate::aocnet::{self, Client};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Where, when and by whom a cached input was fetched; stored next to it as dayNN.meta.yaml.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    pub sha256: String,
+    pub fetched_at: u64,
+    pub account: String,
+    // Size and modification time (nanoseconds since the epoch) of the file when its hash was last checked;
+    // while both still match, the file is taken as unchanged without hashing it again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stamp: Option<(u64, u64)>,
+}
+
+// Result of storing a freshly fetched input.
+#[derive(Debug, Default)]
+pub struct Stored {
+    // Copy of the file that was replaced, if any.
+    pub backup: Option<PathBuf>,
+    // Lines of the replaced file, for diffing against the new input.
+    pub previous: Option<Vec<String>>,
+}
+
+// Takes a day number and returns the expected cached input path for that day.
+pub fn input_path(day: u32) -> PathBuf {
+    year_input_path(aocnet::YEAR, day)
+}
+
+// Takes an event year and day and returns its cache path; years other than this crate's go under input/<year>/.
+pub fn year_input_path(year: u32, day: u32) -> PathBuf {
+    cached_input_path(Path::new("input"), year, day)
+}
+
+// Takes a cache root, event year and day and returns the day's cache path under that root.
+pub fn cached_input_path(root: &Path, year: u32, day: u32) -> PathBuf {
+    let dir = if year == aocnet::YEAR {
+        root.to_path_buf()
+    } else {
+        root.join(year.to_string())
+    };
+    dir.join(format!("day{:02}.txt", day))
+}
+
+// What happened to one day during a bulk fetch.
+#[derive(Debug)]
+pub enum FillStatus {
+    // Already cached before this run; nothing was requested.
+    Cached,
+    // Downloaded and stored; carries the input size in bytes.
+    Fetched(usize),
+    // Not unlocked yet; nothing was requested.
+    Locked(Duration),
+    // The request or the write failed.
+    Failed(String),
+    // Not attempted because an earlier failure made further requests pointless.
+    Skipped,
+}
+
+impl fmt::Display for FillStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FillStatus::Cached => write!(f, "cached"),
+            FillStatus::Fetched(bytes) => write!(f, "fetched ({bytes} bytes)"),
+            FillStatus::Locked(remaining) => {
+                write!(
+                    f,
+                    "locked (unlocks in {})",
+                    aocnet::format_countdown(*remaining)
+                )
+            }
+            FillStatus::Failed(e) => write!(f, "failed: {e}"),
+            FillStatus::Skipped => write!(f, "skipped"),
+        }
+    }
+}
+
+// Takes a client, account, cache root and days, downloads every unlocked day missing from the cache, and returns each day's status.
+//
+// Days already cached are never requested again and writes are atomic, so re-running after an
+// interruption simply resumes with the first day that is still missing. An expired session or a
+// rate limit stops the run; the remaining days are reported as skipped.
+pub fn fill(
+    client: &Client,
+    account: &str,
+    root: &Path,
+    days: impl IntoIterator<Item = u32>,
+    mut progress: impl FnMut(u32, &FillStatus),
+) -> Vec<(u32, FillStatus)> {
+    let year = client.year();
+    let mut report = Vec::new();
+    let mut stop = false;
+
+    for day in days {
+        let path = cached_input_path(root, year, day);
+        let status = if stop {
+            FillStatus::Skipped
+        } else if path.exists() {
+            FillStatus::Cached
+        } else if let Some(remaining) = aocnet::time_until_unlock(year, day, SystemTime::now()) {
+            FillStatus::Locked(remaining)
+        } else {
+            match client.fetch_input(day) {
+                Ok(lines) => match store_input(&path, &lines, account, false) {
+                    Ok(_) => {
+                        FillStatus::Fetched(fs::metadata(&path).map_or(0, |m| m.len() as usize))
+                    }
+                    Err(e) => FillStatus::Failed(e.to_string()),
+                },
+                Err(e) => {
+                    stop = matches!(
+                        e,
+                        aocnet::Error::Unauthorized | aocnet::Error::RateLimited { .. }
+                    );
+                    FillStatus::Failed(format!("{e} ({})", e.advice()))
+                }
+            }
+        };
+        progress(day, &status);
+        report.push((day, status));
+    }
+
+    report
+}
+
+// Takes an input path and returns its provenance sidecar path (dayNN.txt -> dayNN.meta.yaml).
+pub fn provenance_path(input: &Path) -> PathBuf {
+    input.with_extension("meta.yaml")
+}
+
+// Takes an input path and returns where the previous version is kept on refetch (dayNN.txt.bak).
+pub fn backup_path(input: &Path) -> PathBuf {
+    let mut name = input.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    input.with_file_name(name)
+}
+
+// Takes a cache path, reads it line-by-line, and returns the puzzle input lines.
+pub fn read_lines(path: &Path) -> io::Result<Vec<String>> {
+    let f = fs::File::open(path)?;
+    let br = BufReader::new(f);
+    let mut lines = Vec::new();
+    for line in br.lines() {
+        lines.push(line?);
+    }
+    Ok(lines)
+}
+
+// Takes fetched input lines and returns an error if they look like an HTML or login page instead of puzzle input.
+pub fn validate(lines: &[String]) -> Result<(), String> {
+    let Some(first) = lines
+        .iter()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty())
+    else {
+        return Err("response is empty".to_string());
+    };
+
+    let lower = first.to_ascii_lowercase();
+    if lower.starts_with("<!doctype") || lower.starts_with("<html") || lower.starts_with("<head") {
+        return Err("response is an HTML page, not puzzle input".to_string());
+    }
+    if lines.len() <= 2 && lower.contains("log in") {
+        return Err("response asks to log in; the session is not valid".to_string());
+    }
+    Ok(())
+}
+
+// Takes bytes and returns their SHA-256 digest as lowercase hex.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+// Takes a path and contents, writes them to a temporary sibling, syncs, and renames it into place.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(format!(".tmp-{}", std::process::id()));
+    let tmp = path.with_file_name(tmp_name);
+
+    let result = (|| {
+        let mut f = fs::File::create(&tmp)?;
+        f.write_all(contents)?;
+        f.sync_all()?;
+        fs::rename(&tmp, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+// Takes a cache path, fetched lines and the account, validates and writes them atomically with a provenance sidecar.
+pub fn store_input(
+    path: &Path,
+    lines: &[String],
+    account: &str,
+    keep_backup: bool,
+) -> io::Result<Stored> {
+    validate(lines).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+
+    let mut stored = Stored::default();
+    if keep_backup && path.exists() {
+        let backup = backup_path(path);
+        fs::copy(path, &backup)?;
+        stored.previous = Some(read_lines(path)?);
+        stored.backup = Some(backup);
+    }
+
+    write_atomic(path, contents.as_bytes())?;
+
+    let provenance = Provenance {
+        sha256: sha256_hex(contents.as_bytes()),
+        fetched_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        account: account.to_string(),
+        stamp: file_stamp(path),
+    };
+    let yaml = serde_yaml::to_string(&provenance).map_err(io::Error::other)?;
+    write_atomic(&provenance_path(path), yaml.as_bytes())?;
+
+    Ok(stored)
+}
+
+// Takes a cache path and returns its recorded provenance, if a readable sidecar exists.
+pub fn read_provenance(path: &Path) -> Option<Provenance> {
+    let text = fs::read_to_string(provenance_path(path)).ok()?;
+    serde_yaml::from_str(&text).ok()
+}
+
+// Takes a path and returns its size and modification time in nanoseconds since the epoch.
+fn file_stamp(path: &Path) -> Option<(u64, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((metadata.len(), u64::try_from(modified.as_nanos()).ok()?))
+}
+
+// Takes a cache path and returns a warning if the file no longer matches the hash recorded when it was fetched.
+//
+// The file is only hashed when its size or modification time differs from the recorded stamp; a file that still
+// hashes the same gets its stamp refreshed so later checks are cheap again.
+pub fn check_provenance(path: &Path) -> Option<String> {
+    let mut provenance = read_provenance(path)?;
+    let stamp = file_stamp(path);
+    if stamp.is_some() && stamp == provenance.stamp {
+        return None;
+    }
+    let bytes = fs::read(path).ok()?;
+    if sha256_hex(&bytes) == provenance.sha256 {
+        provenance.stamp = stamp;
+        if let Ok(yaml) = serde_yaml::to_string(&provenance) {
+            let _ = write_atomic(&provenance_path(path), yaml.as_bytes());
+        }
+        return None;
+    }
+    Some(format!(
+        "{} changed since it was fetched (SHA-256 differs from {})",
+        path.display(),
+        provenance_path(path).display()
+    ))
+}
+
+// Takes old and new input lines and returns up to `limit` "-"/"+" lines describing where they differ.
+pub fn diff_lines(old: &[String], new: &[String], limit: usize) -> Vec<String> {
+    let mut diff = Vec::new();
+    let mut changed = 0;
+
+    for index in 0..old.len().max(new.len()) {
+        let (before, after) = (old.get(index), new.get(index));
+        if before == after {
+            continue;
+        }
+        changed += 1;
+        if diff.len() + 2 > limit {
+            continue;
+        }
+        if let Some(line) = before {
+            diff.push(format!("{:>5} - {line}", index + 1));
+        }
+        if let Some(line) = after {
+            diff.push(format!("{:>5} + {line}", index + 1));
+        }
+    }
+
+    if changed > 0 {
+        diff.push(format!("{changed} line(s) differ"));
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(items: &[&str]) -> Vec<String> {
+        items.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn rejects_html_and_login_pages() {
+        assert!(validate(&lines(&["L68", "R12"])).is_ok());
+        assert!(validate(&lines(&["<!DOCTYPE html>", "<html lang=\"en-us\">"])).is_err());
+        assert!(validate(&lines(&[
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input."
+        ]))
+        .is_err());
+        assert!(validate(&lines(&["", ""])).is_err());
+    }
+
+    #[test]
+    fn stores_atomically_with_provenance_and_backup() {
+        let dir = std::env::temp_dir().join(format!("aoc2025-cache-{}", std::process::id()));
+        let path = dir.join("day01.txt");
+
+        store_input(&path, &lines(&["L68", "R48"]), "personal", true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "L68\nR48\n");
+        let provenance = read_provenance(&path).unwrap();
+        assert_eq!(provenance.account, "personal");
+        assert_eq!(provenance.sha256, sha256_hex(b"L68\nR48\n"));
+        assert_eq!(provenance.stamp, file_stamp(&path));
+        assert_eq!(check_provenance(&path), None);
+
+        // A sidecar without a stamp (or a touched file) is hashed once and then stamped.
+        let unstamped = Provenance {
+            stamp: None,
+            ..provenance
+        };
+        let yaml = serde_yaml::to_string(&unstamped).unwrap();
+        fs::write(provenance_path(&path), yaml).unwrap();
+        assert_eq!(check_provenance(&path), None);
+        assert_eq!(read_provenance(&path).unwrap().stamp, file_stamp(&path));
+
+        let stored = store_input(&path, &lines(&["L68", "R49"]), "work", true).unwrap();
+        assert_eq!(stored.backup, Some(dir.join("day01.txt.bak")));
+        assert_eq!(
+            fs::read_to_string(dir.join("day01.txt.bak")).unwrap(),
+            "L68\nR48\n"
+        );
+        assert_eq!(stored.previous, Some(lines(&["L68", "R48"])));
+
+        fs::write(&path, "tampered\n").unwrap();
+        assert!(check_provenance(&path).is_some());
+
+        assert!(store_input(&path, &lines(&["<html>"]), "work", true).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "tampered\n");
+
+        let leftovers = fs::read_dir(&dir)
+            .unwrap()
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .contains(".tmp-")
+            })
+            .count();
+        assert_eq!(leftovers, 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn other_years_are_cached_in_their_own_directory() {
+        assert_eq!(
+            year_input_path(aocnet::YEAR, 3),
+            PathBuf::from("input/day03.txt")
+        );
+        assert_eq!(
+            year_input_path(2024, 25),
+            PathBuf::from("input/2024/day25.txt")
+        );
+    }
+
+    #[test]
+    fn diff_reports_changed_lines() {
+        let old = lines(&["a", "b", "c"]);
+        assert!(diff_lines(&old, &old, 10).is_empty());
+        assert_eq!(
+            diff_lines(&old, &lines(&["a", "x"]), 10),
+            vec!["    2 - b", "    2 + x", "    3 - c", "2 line(s) differ"]
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use crate::aocnet::ClientOptions;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// User configuration, read from ~/.config/aoc2025/config.yaml (or $AOC_CONFIG).
+//
+//     contact: "github.com/you/aoc2025 by you@example.com"
+//     min_interval_secs: 5
+//     leaderboard: "123456"
+//     threads: 4
+//     default_account: personal
+//     accounts:
+//       personal: "53616c7465645f5f..."
+//       work: "53616c7465645f5f..."
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub contact: Option<String>,
+    #[serde(default)]
+    pub min_interval_secs: Option<u64>,
+    #[serde(default)]
+    pub leaderboard: Option<String>,
+    #[serde(default)]
+    pub base_url: Option<String>,
+    // Size of the thread pool for parallel solvers; 1 runs them sequentially.
+    #[serde(default)]
+    pub threads: Option<usize>,
+    #[serde(default)]
+    pub default_account: Option<String>,
+    #[serde(default)]
+    pub accounts: BTreeMap<String, String>,
+}
+
+// A session token together with the account name it was resolved from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Session {
+    pub account: String,
+    pub token: String,
+}
+
+impl Config {
+    // Returns the config path from AOC_CONFIG, XDG_CONFIG_HOME or HOME, if one can be determined.
+    pub fn default_path() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os("AOC_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(base.join("aoc2025").join("config.yaml"))
+    }
+
+    // Loads the config from its default path and returns an empty config when there is none.
+    pub fn load_default() -> io::Result<Self> {
+        match Self::default_path() {
+            Some(path) => Self::load(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    // Takes a config path, parses it, checks it is private to the owner if it holds session tokens, and returns
+    // the config.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+
+        if text.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        let config: Self = serde_yaml::from_str(&text).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse {}: {e}", path.display()),
+            )
+        })?;
+        if !config.accounts.is_empty() {
+            check_private(path)?;
+        }
+        Ok(config)
+    }
+
+    // Takes an optional account name and returns the matching session token from the environment or config.
+    pub fn session(&self, account: Option<&str>) -> Result<Session, String> {
+        let env_session = std::env::var("AOC_SESSION")
+            .ok()
+            .filter(|token| !token.trim().is_empty());
+        self.resolve_session(account, env_session)
+    }
+
+    // Picks a session: named account, then AOC_SESSION, then default_account, then the only account.
+    fn resolve_session(
+        &self,
+        account: Option<&str>,
+        env_session: Option<String>,
+    ) -> Result<Session, String> {
+        if let Some(name) = account {
+            return self.account(name);
+        }
+        if let Some(token) = env_session {
+            return Ok(Session {
+                account: "AOC_SESSION".to_string(),
+                token: token.trim().to_string(),
+            });
+        }
+        if let Some(name) = &self.default_account {
+            return self.account(name);
+        }
+        match self.accounts.len() {
+            0 => Err(
+                "no session configured (set AOC_SESSION or add accounts to the config file)"
+                    .to_string(),
+            ),
+            1 => {
+                let name = self.accounts.keys().next().unwrap();
+                self.account(name)
+            }
+            _ => Err(format!(
+                "several accounts configured ({}); pick one with --account or set default_account",
+                self.account_names().join(", ")
+            )),
+        }
+    }
+
+    // Takes an account name and returns its session, or an error listing the known accounts.
+    fn account(&self, name: &str) -> Result<Session, String> {
+        match self.accounts.get(name) {
+            Some(token) => Ok(Session {
+                account: name.to_string(),
+                token: token.trim().to_string(),
+            }),
+            None => Err(format!(
+                "unknown account {name:?} (configured: {})",
+                self.account_names().join(", ")
+            )),
+        }
+    }
+
+    // Returns the configured account names in sorted order.
+    pub fn account_names(&self) -> Vec<&str> {
+        self.accounts.keys().map(String::as_str).collect()
+    }
+
+    // Builds client options from the config, letting the AOC_* environment variables override it.
+    pub fn client_options(&self) -> ClientOptions {
+        let mut options = ClientOptions::default();
+        if let Some(contact) = &self.contact {
+            options.contact = Some(contact.clone());
+        }
+        if let Some(secs) = self.min_interval_secs {
+            options.min_interval = Duration::from_secs(secs);
+        }
+        if let Some(base_url) = &self.base_url {
+            options.base_url = base_url.trim_end_matches('/').to_string();
+        }
+        options.with_env()
+    }
+}
+
+// Takes the config path and returns an error if its permissions let anyone but the owner read it.
+#[cfg(unix)]
+fn check_private(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = fs::metadata(path)?.permissions().mode() & 0o777;
+    if mode & 0o077 != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "{} holds session tokens but has mode {mode:o}; run `chmod 600 {}`",
+                path.display(),
+                path.display()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_private(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_accounts() -> Config {
+        serde_yaml::from_str("default_account: work\naccounts:\n  personal: aaa\n  work: bbb\n")
+            .unwrap()
+    }
+
+    #[test]
+    fn explicit_account_wins_over_environment() {
+        let config = two_accounts();
+        let session = config
+            .resolve_session(Some("personal"), Some("env".to_string()))
+            .unwrap();
+        assert_eq!(session.token, "aaa");
+
+        let session = config
+            .resolve_session(None, Some("env".to_string()))
+            .unwrap();
+        assert_eq!(session.account, "AOC_SESSION");
+
+        let session = config.resolve_session(None, None).unwrap();
+        assert_eq!(session.account, "work");
+        assert!(config.resolve_session(Some("nope"), None).is_err());
+    }
+
+    #[test]
+    fn several_accounts_without_default_are_ambiguous() {
+        let mut config = two_accounts();
+        config.default_account = None;
+        assert!(config.resolve_session(None, None).is_err());
+
+        config.accounts.remove("work");
+        assert_eq!(config.resolve_session(None, None).unwrap().token, "aaa");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_config_readable_by_others() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("aoc2025-config-{}.yaml", std::process::id()));
+        fs::write(&path, "accounts:\n  me: token\n").unwrap();
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        let err = Config::load(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+        assert_eq!(Config::load(&path).unwrap().account_names(), vec!["me"]);
+
+        // Without session tokens there is nothing to keep private.
+        fs::write(&path, "threads: 2\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        assert_eq!(Config::load(&path).unwrap().threads, Some(2));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,16 +1,106 @@
-use crate::days::Solution;
+use crate::days::{Part, Puzzle, Variant};
+use crate::input::{for_each_line, Input, InputShape};
+use crate::parse::{parse_u64, Lines, ParseError};
+use serde::Serialize;
+use std::fmt;
+use std::hint;
+use std::io::{self, BufRead};
+use std::str::FromStr;
 
+// -----------------------------------------------------------
+// Input model
+// -----------------------------------------------------------
+
+// Which way a rotation turns the dial; the discriminant is the sign of its clicks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[repr(i32)]
+pub enum Direction {
+    Left = -1,
+    Right = 1,
+}
+
+// One dial instruction such as "L68".
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Rotation {
+    pub direction: Direction,
+    pub clicks: u32,
+}
+
+impl Rotation {
+    // Returns the rotation as a signed click count: left is negative, right positive.
+    pub fn delta(self) -> i32 {
+        self.direction as i32 * self.clicks as i32
+    }
+}
+
+// The whole puzzle input: one rotation per line.
+pub type Rotations = Lines<Rotation>;
+
+impl FromStr for Rotation {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(line: &str) -> Result<Self, ParseError> {
+        parse_rotation(line.as_bytes().trim_ascii())
+    }
+}
+
+impl fmt::Display for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = match self.direction {
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        };
+        write!(f, "{direction}{}", self.clicks)
+    }
+}
+
+pub struct Day01;
+
+// The rotations as the parts read them.
 #[derive(Default)]
-pub struct Day01 {
+pub struct Parsed {
     // Signed deltas: Rn => +n, Ln => -n
     rotations: Vec<i32>,
+    // Final dial state when the input was streamed instead of stored.
+    streamed: Option<Dial>,
 }
 
-impl Day01 {
-    pub fn new() -> Self {
-        Self::default()
+// Running dial state for streamed input: both parts are counted as each rotation arrives.
+#[derive(Clone, Copy, Debug)]
+struct Dial {
+    position: i32,
+    zero_stops: u64,
+    zero_passes: u64,
+}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Self {
+            position: 50,
+            zero_stops: 0,
+            zero_passes: 0,
+        }
     }
+}
 
+impl Dial {
+    // Takes one signed rotation, turns the dial, and counts whether it stops on and how often it passes zero.
+    fn turn(&mut self, rotation: i32) {
+        let passes = if rotation >= 0 {
+            (self.position + rotation) / 100
+        } else {
+            (-rotation + (100 - self.position) % 100) / 100
+        };
+        self.zero_passes += passes as u64;
+        self.position = Day01::dial_position(self.position + rotation);
+        if self.position == 0 {
+            self.zero_stops += 1;
+        }
+    }
+}
+
+impl Day01 {
     #[inline]
     // Takes any signed dial value, wraps it onto the 0..99 dial, and returns the normalized position.
     fn dial_position(value: i32) -> i32 {
@@ -22,34 +112,112 @@ impl Day01 {
     }
 }
 
-impl Solution for Day01 {
-    // Takes raw rotation instructions, parses them as signed click counts, and stores them for both parts.
-    fn set_input(&mut self, lines: &[String]) {
-        self.rotations.clear();
+// Largest click count a rotation may have, leaving room to add a dial position (0 to 99) without overflowing i32.
+const MAX_CLICKS: u64 = i32::MAX as u64 - 100;
 
-        for line in lines {
-            let instruction = line.trim();
-            if instruction.is_empty() {
-                continue;
-            }
+// Takes one trimmed instruction line such as "L68" and returns the rotation.
+#[inline]
+fn parse_rotation(line: &[u8]) -> Result<Rotation, ParseError> {
+    let Some((&letter, digits)) = line.split_first() else {
+        return Err(ParseError::invalid(
+            "rotations must start with L or R",
+            line,
+        ));
+    };
+    // L and R arrive in random order, so only the (predictable) validity check may branch.
+    if (letter != b'L') & (letter != b'R') {
+        return Err(ParseError::invalid(
+            "rotations must start with L or R",
+            line,
+        ));
+    }
+    let direction = hint::select_unpredictable(letter == b'L', Direction::Left, Direction::Right);
+    match parse_u64(digits) {
+        Some(clicks) if clicks <= MAX_CLICKS => Ok(Rotation {
+            direction,
+            clicks: clicks as u32,
+        }),
+        _ => Err(ParseError::invalid(
+            &format!("rotation clicks must be a number up to {MAX_CLICKS}"),
+            line,
+        )),
+    }
+}
 
-            let (direction, rest) = instruction.split_at(1);
-            let clicks: i32 = rest.parse().unwrap();
+impl Puzzle for Day01 {
+    type Input = Parsed;
 
-            if direction == "L" {
-                self.rotations.push(-clicks);
-            } else {
-                self.rotations.push(clicks);
+    const PARTS: &'static [Part<Self>] = &[
+        Part::new(
+            1,
+            "zero-stops",
+            "Rotations that leave the dial pointing at zero",
+            Self::part1,
+        ),
+        Part::new(
+            2,
+            "zero-passes",
+            "Clicks that move the dial onto zero",
+            Self::part2,
+        ),
+    ];
+    const VARIANTS: &'static [Variant<Self>] = &[Variant::new(
+        2,
+        "click-simulation",
+        "Turns the dial one click at a time",
+        Self::part2_by_clicks,
+    )];
+
+    // Expects one rotation per line.
+    fn input_shape() -> InputShape {
+        InputShape::Lines
+    }
+
+    // Takes raw rotation instructions and returns them as signed click counts.
+    //
+    // Parses line by line with the same parser as `Rotations`, skipping its intermediate Vec.
+    fn parse(input: &Input) -> Result<Parsed, ParseError> {
+        let mut rotations = Vec::new();
+        for (index, line) in input.byte_lines().enumerate() {
+            let line = line.trim_ascii();
+            if !line.is_empty() {
+                let rotation = parse_rotation(line).map_err(|e| e.at_line(index + 1))?;
+                rotations.push(rotation.delta());
             }
         }
+        Ok(Parsed {
+            rotations,
+            streamed: None,
+        })
     }
 
+    // Takes a reader over the rotations and turns the dial as each one arrives, keeping only the counts.
+    fn stream(reader: &mut dyn BufRead) -> io::Result<Parsed> {
+        let mut dial = Dial::default();
+        for_each_line(reader, |_, line| {
+            let line = line.trim();
+            if !line.is_empty() {
+                dial.turn(parse_rotation(line.as_bytes())?.delta());
+            }
+            Ok(())
+        })?;
+        Ok(Parsed {
+            rotations: Vec::new(),
+            streamed: Some(dial),
+        })
+    }
+}
+
+impl Day01 {
     // Applies each full rotation from the starting position and returns how many rotations end at zero.
-    fn part1(&mut self) -> String {
+    fn part1(input: &Parsed) -> String {
+        if let Some(dial) = &input.streamed {
+            return dial.zero_stops.to_string();
+        }
         let mut position: i32 = 50;
         let mut zero_hits = 0;
 
-        for &rotation in &self.rotations {
+        for &rotation in &input.rotations {
             position = Self::dial_position(position + rotation);
             if position == 0 {
                 zero_hits += 1;
@@ -60,76 +228,137 @@ impl Solution for Day01 {
     }
 
     // Counts zero crossings arithmetically for each rotation and returns their total.
-    fn part2(&mut self) -> String {
+    fn part2(input: &Parsed) -> String {
+        if let Some(dial) = &input.streamed {
+            return dial.zero_passes.to_string();
+        }
         let mut position: i32 = 50;
-        let mut zero_hits = 0;
+        let mut zero_hits: u64 = 0;
 
-        for &rotation in &self.rotations {
-            if rotation >= 0 {
-                zero_hits += (position + rotation) / 100;
+        for &rotation in &input.rotations {
+            let passes = if rotation >= 0 {
+                (position + rotation) / 100
             } else {
                 let distance = -rotation;
                 let clicks_to_zero = (100 - position) % 100;
-                zero_hits += (distance + clicks_to_zero) / 100;
-            }
+                (distance + clicks_to_zero) / 100
+            };
+            zero_hits += passes as u64;
 
             position = Self::dial_position(position + rotation);
         }
 
         zero_hits.to_string()
     }
+
+    // Turns the dial one click at a time and returns how often it lands on zero: the reference for `part2`.
+    fn part2_by_clicks(input: &Parsed) -> String {
+        // Streamed input keeps only the counts, so there are no rotations left to simulate.
+        assert!(
+            input.streamed.is_none(),
+            "the click-simulation variant needs stored input and cannot run on streamed input"
+        );
+        let mut position: i32 = 50;
+        let mut zero_hits: u64 = 0;
+
+        for &rotation in &input.rotations {
+            let step = rotation.signum();
+            for _ in 0..rotation.unsigned_abs() {
+                position = Self::dial_position(position + step);
+                zero_hits += u64::from(position == 0);
+            }
+        }
+
+        zero_hits.to_string()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::days::Solution;
 
-    fn example_input() -> Vec<String> {
-        vec![
+    fn example_input() -> Input {
+        Input::from_lines(&[
             "L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82",
-        ]
-        .into_iter()
-        .map(|s| s.to_string())
-        .collect()
+        ])
     }
 
     #[test]
     fn part1_example() {
-        let mut d = Day01::new();
-        d.set_input(&example_input());
-        assert_eq!(d.part1(), "3");
+        let input = Day01::parse(&example_input()).unwrap();
+        assert_eq!(Day01::part1(&input), "3");
     }
 
     #[test]
     fn part2_example() {
-        let mut d = Day01::new();
-        d.set_input(&example_input());
-        assert_eq!(d.part2(), "6");
+        let input = Day01::parse(&example_input()).unwrap();
+        assert_eq!(Day01::part2(&input), "6");
     }
 
     #[test]
-    fn arithmetic_crossing_count_matches_click_simulation() {
-        fn simulate(rotations: &[i32]) -> i32 {
-            let mut position = 50;
-            let mut hits = 0;
-            for &rotation in rotations {
-                let step = rotation.signum();
-                for _ in 0..rotation.unsigned_abs() {
-                    position = Day01::dial_position(position + step);
-                    hits += i32::from(position == 0);
-                }
+    fn input_model_round_trips() {
+        let rotations: Rotations = example_input().as_str().parse().unwrap();
+        assert_eq!(rotations.0.len(), 10);
+        assert_eq!(
+            rotations.0[0],
+            Rotation {
+                direction: Direction::Left,
+                clicks: 68
             }
-            hits
-        }
+        );
+        assert_eq!(rotations.to_string(), example_input().as_str());
 
+        let error = "L1\nX5\n".parse::<Rotations>().unwrap_err();
+        assert_eq!(error.line(), Some(2));
+    }
+
+    #[test]
+    fn streamed_input_gives_the_same_answers() {
+        let streamed = Day01::stream(&mut example_input().as_bytes()).unwrap();
+        assert_eq!(
+            (Day01::part1(&streamed), Day01::part2(&streamed)),
+            ("3".to_string(), "6".to_string())
+        );
+
+        let error = Day01::parse(&Input::from("L1\nR2\nX5\n")).err().unwrap();
+        assert_eq!(error.line(), Some(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot run on streamed input")]
+    fn click_simulation_is_unavailable_for_streamed_input() {
+        let streamed = Day01::stream(&mut example_input().as_bytes()).unwrap();
+        Day01::part2_by_clicks(&streamed);
+    }
+
+    #[test]
+    fn the_largest_rotations_do_not_overflow() {
+        let text = format!("L{MAX_CLICKS}\nR{MAX_CLICKS}\nR{MAX_CLICKS}\n");
+        let input = Day01::parse(&Input::from(text.as_str())).unwrap();
+        let streamed = Day01::stream(&mut text.as_bytes()).unwrap();
+        assert_eq!(Day01::part2(&input), Day01::part2(&streamed));
+        assert_eq!(Day01::part1(&input), Day01::part1(&streamed));
+
+        let too_many = format!("L{}", MAX_CLICKS + 1);
+        assert!(Day01::parse(&Input::from(too_many.as_str())).is_err());
+    }
+
+    #[test]
+    fn streaming_a_malformed_line_is_an_error() {
+        let error = Day01::stream(&mut "L1\nX5\n".as_bytes()).err().unwrap();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(error.to_string().starts_with("line 2: "), "{error}");
+    }
+
+    #[test]
+    fn arithmetic_crossing_count_matches_click_simulation() {
         for first in (-250..=250).step_by(17) {
             for second in (-250..=250).step_by(29) {
-                let rotations = [first, second, -first / 2];
-                let mut day = Day01 {
-                    rotations: rotations.to_vec(),
+                let input = Parsed {
+                    rotations: vec![first, second, -first / 2],
+                    streamed: None,
                 };
-                assert_eq!(day.part2(), simulate(&rotations).to_string());
+                assert_eq!(Day01::part2(&input), Day01::part2_by_clicks(&input));
             }
         }
     }
@@ -1,16 +1,73 @@
-use crate::days::Solution;
+use crate::days::{Part, Puzzle};
+use crate::input::{Input, InputShape};
+use crate::parse::{self, ParseError};
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
 
-#[derive(Default)]
-pub struct Day02 {
-    id_ranges: Vec<(i64, i64)>,
+// ------------------------------------------------------------
+// Input model
+// ------------------------------------------------------------
+
+// One inclusive product ID range such as "11-22".
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct IdRange {
+    pub start: i64,
+    pub end: i64,
 }
 
-impl Day02 {
-    pub fn new() -> Self {
-        Self::default()
+// The whole puzzle input: one line of comma-separated ID ranges.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct IdRanges(pub Vec<IdRange>);
+
+impl FromStr for IdRange {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, ParseError> {
+        parse::parse_range(text.trim().as_bytes())
+            .filter(|&(start, end)| start <= end && end <= i64::MAX as u64)
+            .map(|(start, end)| Self {
+                start: start as i64,
+                end: end as i64,
+            })
+            .ok_or_else(|| ParseError::new(format!("ID ranges must look like 11-22: {text:?}")))
+    }
+}
+
+impl fmt::Display for IdRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
     }
 }
 
+impl FromStr for IdRanges {
+    type Err = ParseError;
+
+    // Empty fields are skipped, so a trailing comma is accepted.
+    fn from_str(text: &str) -> Result<Self, ParseError> {
+        text.trim()
+            .split(',')
+            .filter(|range| !range.trim().is_empty())
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl fmt::Display for IdRanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, range) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{range}")?;
+        }
+        writeln!(f)
+    }
+}
+
+pub struct Day02;
+
 // ------------------------------------------------------------
 // Helpers
 // ------------------------------------------------------------
@@ -56,32 +113,42 @@ fn smallest_repeating_block_len(digits: &str) -> usize {
 // Solution impl
 // ------------------------------------------------------------
 
-impl Solution for Day02 {
-    // Takes the comma-separated ID range line, parses inclusive bounds, and stores them for enumeration.
-    fn set_input(&mut self, lines: &[String]) {
-        self.id_ranges.clear();
-
-        if lines.is_empty() {
-            return;
-        }
+impl Puzzle for Day02 {
+    type Input = IdRanges;
+
+    const PARTS: &'static [Part<Self>] = &[
+        Part::new(
+            1,
+            "doubled-ids",
+            "Sum of the invalid IDs made of one block repeated twice",
+            Self::part1,
+        ),
+        Part::new(
+            2,
+            "repeated-ids",
+            "Sum of the invalid IDs made of one block repeated at least twice",
+            Self::part2,
+        ),
+    ];
+
+    // Expects all ranges on one comma-separated line.
+    fn input_shape() -> InputShape {
+        InputShape::SingleLine
+    }
 
-        let line = lines[0].trim();
-        for part in line.split(',') {
-            if part.is_empty() {
-                continue;
-            }
-            let mut it = part.split('-');
-            let start: i64 = it.next().unwrap().parse().unwrap();
-            let end: i64 = it.next().unwrap().parse().unwrap();
-            self.id_ranges.push((start, end));
-        }
+    // Takes the comma-separated ID range line and returns its inclusive bounds for enumeration.
+    fn parse(input: &Input) -> Result<IdRanges, ParseError> {
+        input.as_str().parse()
     }
+}
 
+impl Day02 {
     // Enumerates IDs formed by repeating a block exactly twice and returns their sum across all ranges.
-    fn part1(&mut self) -> String {
+    fn part1(input: &IdRanges) -> String {
         let mut sum: i64 = 0;
 
-        for &(range_start, range_end) in &self.id_ranges {
+        for range in &input.0 {
+            let (range_start, range_end) = (range.start, range.end);
             let max_digits = range_end.to_string().len();
 
             for block_digits in 1..=max_digits / 2 {
@@ -114,10 +181,11 @@ impl Solution for Day02 {
     }
 
     // Enumerates IDs formed by repeating any primitive block multiple times and returns their summed value.
-    fn part2(&mut self) -> String {
+    fn part2(input: &IdRanges) -> String {
         let mut total: i64 = 0;
 
-        for &(range_start, range_end) in &self.id_ranges {
+        for range in &input.0 {
+            let (range_start, range_end) = (range.start, range.end);
             let max_digits = range_end.to_string().len();
 
             for (total_digits, &ten_len) in POW10.iter().enumerate().take(max_digits + 1).skip(2) {
@@ -170,26 +238,44 @@ impl Solution for Day02 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::days::Solution;
 
-    fn example_input() -> Vec<String> {
-        vec!["11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
+    fn example_input() -> Input {
+        Input::from(
+            "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
 1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
-824824821-824824827,2121212118-2121212124"
-            .to_string()]
+824824821-824824827,2121212118-2121212124",
+        )
     }
 
     #[test]
     fn part1_example() {
-        let mut d = Day02::new();
-        d.set_input(&example_input());
-        assert_eq!(d.part1(), "1227775554");
+        let input = Day02::parse(&example_input()).unwrap();
+        assert_eq!(Day02::part1(&input), "1227775554");
     }
 
     #[test]
     fn part2_example() {
-        let mut d = Day02::new();
-        d.set_input(&example_input());
-        assert_eq!(d.part2(), "4174379265");
+        let input = Day02::parse(&example_input()).unwrap();
+        assert_eq!(Day02::part2(&input), "4174379265");
+    }
+
+    #[test]
+    fn input_model_round_trips() {
+        let ranges: IdRanges = example_input().as_str().parse().unwrap();
+        assert_eq!(ranges.0.len(), 11);
+        assert_eq!(
+            ranges.0[1],
+            IdRange {
+                start: 95,
+                end: 115
+            }
+        );
+        assert_eq!(
+            ranges.to_string(),
+            format!("{}\n", example_input().as_str())
+        );
+
+        assert!("11-22,95".parse::<IdRanges>().is_err());
+        assert!("22-11".parse::<IdRanges>().is_err());
     }
 }
@@ -1,107 +1,255 @@
-use crate::days::Solution;
-
-#[derive(Default)]
-pub struct Day03 {
-    battery_banks: Vec<Vec<u8>>,
+use crate::days::{Part, Puzzle};
+use crate::input::{self, for_each_line, Input, InputShape};
+use crate::parse::{Grid, ParseError};
+use serde::Serialize;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+// The whole puzzle input: one row of digit values (0..=9) per battery bank, all the same length.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct BatteryBanks(pub Grid);
+
+const BANKS_ERROR: &str = "battery banks must be equal-length rows of digits";
+
+impl FromStr for BatteryBanks {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, ParseError> {
+        Grid::parse_digits(input::byte_lines(text.as_bytes()))
+            .map(Self)
+            .ok_or_else(|| ParseError::new(BANKS_ERROR))
+    }
 }
 
-impl Day03 {
-    pub fn new() -> Self {
-        Self::default()
+impl fmt::Display for BatteryBanks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write_rows(f, |digit| char::from(b'0' + digit))
     }
+}
+
+pub struct Day03;
 
+// The battery banks as the parts read them.
+#[derive(Default)]
+pub struct Parsed {
+    // One row of digit values per bank.
+    battery_banks: Grid,
+    // Part 1 and part 2 totals when the input was streamed instead of stored; wide enough for inputs of any size.
+    streamed: Option<[u128; 2]>,
+}
+
+impl Parsed {
     // Takes the number of batteries to choose per bank, greedily forms each maximum number, and returns their sum.
     fn max_joltage(&self, batteries_to_pick: usize) -> String {
-        let mut total: i64 = 0;
-
-        for bank in &self.battery_banks {
-            let bank_len = bank.len();
-            let mut remaining_picks = batteries_to_pick;
-            let mut chosen_digits: Vec<u8> = Vec::with_capacity(batteries_to_pick);
-
-            for (index, &digit) in bank.iter().enumerate() {
-                let remaining_digits = bank_len - index;
-
-                while !chosen_digits.is_empty()
-                    && remaining_digits > remaining_picks
-                    && *chosen_digits.last().unwrap() < digit
-                {
-                    chosen_digits.pop();
-                    remaining_picks += 1;
-                }
-
-                if remaining_picks > 0 {
-                    chosen_digits.push(digit);
-                    remaining_picks -= 1;
-                }
-            }
+        let total: u128 = self
+            .battery_banks
+            .row_iter()
+            .map(|bank| u128::from(bank_joltage(bank, batteries_to_pick)))
+            .sum();
+        total.to_string()
+    }
+}
 
-            total += digits_to_number(&chosen_digits);
+// Takes one bank's digits and the number of batteries to pick, and returns the largest joltage they can form.
+fn bank_joltage(bank: &[u8], batteries_to_pick: usize) -> u64 {
+    let bank_len = bank.len();
+    let mut remaining_picks = batteries_to_pick;
+    let mut chosen_digits: Vec<u8> = Vec::with_capacity(batteries_to_pick);
+
+    for (index, &digit) in bank.iter().enumerate() {
+        let remaining_digits = bank_len - index;
+
+        while !chosen_digits.is_empty()
+            && remaining_digits > remaining_picks
+            && *chosen_digits.last().unwrap() < digit
+        {
+            chosen_digits.pop();
+            remaining_picks += 1;
         }
 
-        total.to_string()
+        if remaining_picks > 0 {
+            chosen_digits.push(digit);
+            remaining_picks -= 1;
+        }
     }
+
+    digits_to_number(&chosen_digits)
 }
 
 // Takes selected decimal digits in order, folds them into a number, and returns that joltage value.
-fn digits_to_number(digits: &[u8]) -> i64 {
-    let mut value: i64 = 0;
+fn digits_to_number(digits: &[u8]) -> u64 {
+    let mut value: u64 = 0;
     for &digit in digits {
-        value = value * 10 + digit as i64;
+        value = value * 10 + u64::from(digit);
     }
     value
 }
 
-impl Solution for Day03 {
-    // Takes one digit string per battery bank, converts each character to a digit, and stores all banks.
-    fn set_input(&mut self, lines: &[String]) {
-        self.battery_banks.clear();
+impl Puzzle for Day03 {
+    type Input = Parsed;
+
+    const PARTS: &'static [Part<Self>] = &[
+        Part::new(
+            1,
+            "two-batteries",
+            "Total output joltage turning on two batteries per bank",
+            Self::part1,
+        ),
+        Part::new(
+            2,
+            "twelve-batteries",
+            "Total output joltage turning on twelve batteries per bank",
+            Self::part2,
+        ),
+    ];
+
+    // Expects one equal-length row of digits per bank.
+    fn input_shape() -> InputShape {
+        InputShape::Grid { ragged: false }
+    }
 
-        for line in lines {
-            let digits = line.bytes().map(|b| b - b'0').collect::<Vec<_>>();
-            self.battery_banks.push(digits);
-        }
+    // Takes one digit string per battery bank and returns all banks as one grid of digit values.
+    fn parse(input: &Input) -> Result<Parsed, ParseError> {
+        let BatteryBanks(battery_banks) = input.as_str().parse()?;
+        Ok(Parsed {
+            battery_banks,
+            streamed: None,
+        })
+    }
+
+    // Takes a reader over the banks and adds each bank's best joltages as it arrives, keeping one bank in memory.
+    fn stream(reader: &mut dyn BufRead) -> io::Result<Parsed> {
+        let mut totals = [0u128; 2];
+        let mut digits = Vec::new();
+        for_each_line(reader, |_, line| {
+            digits.clear();
+            if !line.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(ParseError::new(BANKS_ERROR));
+            }
+            digits.extend(line.bytes().map(|b| b - b'0'));
+            totals[0] += u128::from(bank_joltage(&digits, 2));
+            totals[1] += u128::from(bank_joltage(&digits, 12));
+            Ok(())
+        })?;
+        Ok(Parsed {
+            battery_banks: Grid::default(),
+            streamed: Some(totals),
+        })
     }
+}
 
+impl Day03 {
     // Chooses two batteries from each bank and returns the total maximum joltage.
-    fn part1(&mut self) -> String {
-        self.max_joltage(2)
+    fn part1(input: &Parsed) -> String {
+        if let Some([total, _]) = input.streamed {
+            return total.to_string();
+        }
+        input.max_joltage(2)
     }
 
     // Chooses twelve batteries from each bank and returns the total maximum joltage.
-    fn part2(&mut self) -> String {
-        self.max_joltage(12)
+    fn part2(input: &Parsed) -> String {
+        if let Some([_, total]) = input.streamed {
+            return total.to_string();
+        }
+        input.max_joltage(12)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::days::Solution;
 
-    fn example_input() -> Vec<String> {
-        vec![
+    fn example_input() -> Input {
+        Input::from_lines(&[
             "987654321111111",
             "811111111111119",
             "234234234234278",
             "818181911112111",
-        ]
-        .into_iter()
-        .map(|s| s.to_string())
-        .collect()
+        ])
     }
 
     #[test]
     fn part1_example() {
-        let mut d = Day03::new();
-        d.set_input(&example_input());
-        assert_eq!(d.part1(), "357");
+        let input = Day03::parse(&example_input()).unwrap();
+        assert_eq!(Day03::part1(&input), "357");
     }
 
     #[test]
     fn part2_example() {
-        let mut d = Day03::new();
-        d.set_input(&example_input());
-        assert_eq!(d.part2(), "3121910778619");
+        let input = Day03::parse(&example_input()).unwrap();
+        assert_eq!(Day03::part2(&input), "3121910778619");
+    }
+
+    #[test]
+    fn input_model_round_trips() {
+        let banks: BatteryBanks = example_input().as_str().parse().unwrap();
+        assert_eq!((banks.0.rows, banks.0.cols), (4, 15));
+        assert_eq!(&banks.0.row(1)[..3], &[8, 1, 1]);
+        assert_eq!(banks.to_string(), example_input().as_str());
+
+        assert!("987\n81\n".parse::<BatteryBanks>().is_err());
+    }
+
+    #[test]
+    fn streamed_input_gives_the_same_answers() {
+        let stored = Day03::parse(&example_input()).unwrap();
+        let streamed = Day03::stream(&mut example_input().as_bytes()).unwrap();
+        assert_eq!(
+            (Day03::part1(&streamed), Day03::part2(&streamed)),
+            (Day03::part1(&stored), Day03::part2(&stored))
+        );
+    }
+
+    #[test]
+    fn streaming_rejects_non_digits_like_parsing() {
+        let text = "12345\n12x45\n";
+        let parsed = Day03::parse(&Input::from(text)).err().unwrap();
+        let streamed = Day03::stream(&mut text.as_bytes()).err().unwrap();
+        assert_eq!(streamed.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(streamed.to_string(), parsed.at_line(2).to_string());
+    }
+
+    // Reads the same bank over and over, so a huge input can be streamed without building it in memory.
+    struct RepeatedBank {
+        bank: &'static [u8],
+        remaining: usize,
+        offset: usize,
+    }
+
+    impl std::io::Read for RepeatedBank {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let mut written = 0;
+            while written < buf.len() && self.remaining > 0 {
+                let rest = &self.bank[self.offset..];
+                let n = rest.len().min(buf.len() - written);
+                buf[written..written + n].copy_from_slice(&rest[..n]);
+                written += n;
+                self.offset += n;
+                if self.offset == self.bank.len() {
+                    self.offset = 0;
+                    self.remaining -= 1;
+                }
+            }
+            Ok(written)
+        }
+    }
+
+    #[test]
+    #[ignore = "streams nine million banks; run with `cargo test --release -- --ignored`"]
+    fn streamed_totals_do_not_overflow() {
+        // Each bank adds 999_999_999_999 to part 2, so this many push the total past i64::MAX.
+        let banks = (i64::MAX as u64 / 999_999_999_999 + 1) as usize;
+        let reader = RepeatedBank {
+            bank: b"999999999999\n",
+            remaining: banks,
+            offset: 0,
+        };
+        let streamed = Day03::stream(&mut std::io::BufReader::new(reader)).unwrap();
+        let expected = banks as u128 * 999_999_999_999;
+        assert!(expected > i64::MAX as u128);
+        assert_eq!(Day03::part2(&streamed), expected.to_string());
+        assert_eq!(Day03::part1(&streamed), (banks as u128 * 99).to_string());
     }
 }
@@ -1,17 +1,48 @@
-use crate::days::Solution;
+use crate::days::{Part, Puzzle, Variant};
+use crate::input::{self, Input, InputShape};
+use crate::parse::{find_other_byte, Grid, ParseError};
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
 
+// The whole puzzle input: the map of paper rolls ('@') and empty floor ('.'), all rows the same width.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct PaperRolls(#[serde(serialize_with = "Grid::serialize_text")] pub Grid);
+
+impl FromStr for PaperRolls {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse(input::byte_lines(text.as_bytes())).ok_or_else(|| {
+            ParseError::new("the paper roll map's rows must all be the same width")
+        })?;
+        if let Some(other) = find_other_byte(&grid.cells, [b'@', b'.']) {
+            return Err(ParseError::new(format!(
+                "the paper roll map may only contain '@' and '.', found {:?}",
+                char::from(other)
+            )));
+        }
+        Ok(Self(grid))
+    }
+}
+
+impl fmt::Display for PaperRolls {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write_rows(f, char::from)
+    }
+}
+
+pub struct Day04;
+
+// The paper-roll grid as the parts read it.
 #[derive(Default)]
-pub struct Day04 {
+pub struct Parsed {
     grid: Vec<u8>,
     rows: usize,
     cols: usize,
 }
 
-impl Day04 {
-    pub fn new() -> Self {
-        Self::default()
-    }
-
+impl Parsed {
     // 8 directions
     const DIRS: [(isize, isize); 8] = [
         (-1, -1),
@@ -77,31 +108,59 @@ impl Day04 {
     }
 }
 
-impl Solution for Day04 {
-    // Takes the paper-roll map, stores it as bytes, and records its dimensions.
-    fn set_input(&mut self, lines: &[String]) {
-        self.grid.clear();
+impl Puzzle for Day04 {
+    type Input = Parsed;
 
-        self.rows = lines.len();
-        self.cols = lines.first().map_or(0, String::len);
-        self.grid.reserve(self.rows * self.cols);
-        for line in lines {
-            self.grid.extend_from_slice(line.as_bytes());
-        }
+    const PARTS: &'static [Part<Self>] = &[
+        Part::new(
+            1,
+            "accessible-rolls",
+            "Paper rolls a forklift can reach right away",
+            Self::part1,
+        ),
+        Part::new(
+            2,
+            "removable-rolls",
+            "Paper rolls removed by taking accessible ones until none are left",
+            Self::part2,
+        ),
+    ];
+    const VARIANTS: &'static [Variant<Self>] = &[Variant::new(
+        2,
+        "repeated-scans",
+        "Rescans the whole grid after every round of removals",
+        Self::part2_by_scans,
+    )];
+
+    // Expects the equal-width grid of paper rolls.
+    fn input_shape() -> InputShape {
+        InputShape::Grid { ragged: false }
     }
 
+    // Takes the paper-roll map and returns it as bytes with its dimensions.
+    fn parse(input: &Input) -> Result<Parsed, ParseError> {
+        let PaperRolls(grid) = input.as_str().parse()?;
+        Ok(Parsed {
+            grid: grid.cells,
+            rows: grid.rows,
+            cols: grid.cols,
+        })
+    }
+}
+
+impl Day04 {
     // Counts rolls immediately accessible under the adjacency rule and returns that count.
-    fn part1(&mut self) -> String {
-        if self.rows == 0 || self.cols == 0 {
+    fn part1(input: &Parsed) -> String {
+        if input.rows == 0 || input.cols == 0 {
             return "0".to_string();
         }
 
         let mut total = 0;
 
-        for row in 0..self.rows {
-            for col in 0..self.cols {
-                if self.grid[row * self.cols + col] == b'@'
-                    && self.count_adjacent_rolls(row, col) < 4
+        for row in 0..input.rows {
+            for col in 0..input.cols {
+                if input.grid[row * input.cols + col] == b'@'
+                    && input.count_adjacent_rolls(row, col) < 4
                 {
                     total += 1;
                 }
@@ -112,19 +171,19 @@ impl Solution for Day04 {
     }
 
     // Repeatedly removes accessible rolls, updates neighbor counts, and returns the total removed.
-    fn part2(&mut self) -> String {
-        if self.rows == 0 || self.cols == 0 {
+    fn part2(input: &Parsed) -> String {
+        if input.rows == 0 || input.cols == 0 {
             return "0".to_string();
         }
 
-        let mut has_roll = self.paper_roll_grid();
-        let mut neighbor_counts = self.count_neighbor_rolls(&has_roll);
+        let mut has_roll = input.paper_roll_grid();
+        let mut neighbor_counts = input.count_neighbor_rolls(&has_roll);
 
         let mut queue: Vec<(usize, usize)> = Vec::new();
 
-        for row in 0..self.rows {
-            for col in 0..self.cols {
-                let index = row * self.cols + col;
+        for row in 0..input.rows {
+            for col in 0..input.cols {
+                let index = row * input.cols + col;
                 if has_roll[index] && neighbor_counts[index] < 4 {
                     queue.push((row, col));
                 }
@@ -137,7 +196,7 @@ impl Solution for Day04 {
         while queue_pos < queue.len() {
             let (row, col) = queue[queue_pos];
             queue_pos += 1;
-            let index = row * self.cols + col;
+            let index = row * input.cols + col;
 
             if !has_roll[index] {
                 continue;
@@ -146,19 +205,19 @@ impl Solution for Day04 {
             has_roll[index] = false;
             removed += 1;
 
-            for (dr, dc) in Self::DIRS {
+            for (dr, dc) in Parsed::DIRS {
                 let neighbor_row = row as isize + dr;
                 let neighbor_col = col as isize + dc;
                 if neighbor_row < 0
-                    || neighbor_row >= self.rows as isize
+                    || neighbor_row >= input.rows as isize
                     || neighbor_col < 0
-                    || neighbor_col >= self.cols as isize
+                    || neighbor_col >= input.cols as isize
                 {
                     continue;
                 }
                 let neighbor_row = neighbor_row as usize;
                 let neighbor_col = neighbor_col as usize;
-                let neighbor_index = neighbor_row * self.cols + neighbor_col;
+                let neighbor_index = neighbor_row * input.cols + neighbor_col;
 
                 if !has_roll[neighbor_index] {
                     continue;
@@ -173,15 +232,37 @@ impl Solution for Day04 {
 
         removed.to_string()
     }
+
+    // Removes every accessible roll found by a full scan, repeating until a scan finds none, and returns the total
+    // removed: the reference for `part2`.
+    fn part2_by_scans(input: &Parsed) -> String {
+        let mut has_roll = input.paper_roll_grid();
+        let mut removed = 0;
+
+        loop {
+            let neighbor_counts = input.count_neighbor_rolls(&has_roll);
+            let accessible: Vec<usize> = (0..has_roll.len())
+                .filter(|&index| has_roll[index] && neighbor_counts[index] < 4)
+                .collect();
+            if accessible.is_empty() {
+                break;
+            }
+            removed += accessible.len();
+            for index in accessible {
+                has_roll[index] = false;
+            }
+        }
+
+        removed.to_string()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::days::Solution;
 
-    fn example_input() -> Vec<String> {
-        vec![
+    fn example_input() -> Input {
+        Input::from_lines(&[
             "..@@.@@@@.",
             "@@@.@.@.@@",
             "@@@@@.@.@@",
@@ -192,23 +273,38 @@ mod tests {
             "@.@@@.@@@@",
             ".@@@@@@@@.",
             "@.@.@@@.@.",
-        ]
-        .into_iter()
-        .map(|s| s.to_string())
-        .collect()
+        ])
     }
 
     #[test]
     fn part1_example() {
-        let mut d = Day04::new();
-        d.set_input(&example_input());
-        assert_eq!(d.part1(), "13");
+        let input = Day04::parse(&example_input()).unwrap();
+        assert_eq!(Day04::part1(&input), "13");
     }
 
     #[test]
     fn part2_example() {
-        let mut d = Day04::new();
-        d.set_input(&example_input());
-        assert_eq!(d.part2(), "43");
+        let input = Day04::parse(&example_input()).unwrap();
+        assert_eq!(Day04::part2(&input), "43");
+    }
+
+    #[test]
+    fn queue_matches_repeated_scans() {
+        let input = Day04::parse(&example_input()).unwrap();
+        assert_eq!(Day04::part2_by_scans(&input), "43");
+
+        let lines = crate::inputgen::generate(4, 40, crate::inputgen::DEFAULT_SEED).unwrap();
+        let input = Day04::parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(Day04::part2(&input), Day04::part2_by_scans(&input));
+    }
+
+    #[test]
+    fn input_model_round_trips() {
+        let rolls: PaperRolls = example_input().as_str().parse().unwrap();
+        assert_eq!((rolls.0.rows, rolls.0.cols), (10, 10));
+        assert_eq!(rolls.to_string(), example_input().as_str());
+
+        assert!("..@\n.@\n".parse::<PaperRolls>().is_err());
+        assert!("..@\n.#.\n".parse::<PaperRolls>().is_err());
     }
 }
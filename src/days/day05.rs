@@ -1,16 +1,80 @@
-use crate::days::Solution;
+use crate::days::{Part, Puzzle};
+use crate::input::{self, for_each_line, Input, InputShape};
+use crate::parse::{self, parse_u64, ParseError};
+use serde::Serialize;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::str::FromStr;
 
+// -----------------------------------------------------------
+// Input model
+// -----------------------------------------------------------
+
+// One inclusive range of fresh ingredient IDs such as "3-5".
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct FreshRange {
+    pub start: i64,
+    pub end: i64,
+}
+
+// The whole puzzle input: the fresh ranges, a blank line, then the available ingredient IDs.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Inventory {
+    pub fresh_ranges: Vec<FreshRange>,
+    pub available_ids: Vec<i64>,
+}
+
+impl FromStr for Inventory {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, ParseError> {
+        let mut inventory = Self::default();
+        let mut loading_ranges = true;
+        for (index, line) in input::byte_lines(text.as_bytes()).enumerate() {
+            let s = line.trim_ascii();
+            if s.is_empty() {
+                // The first blank line after some ranges starts the IDs.
+                if !inventory.fresh_ranges.is_empty() {
+                    loading_ranges = false;
+                }
+                continue;
+            }
+            let parsed = if loading_ranges {
+                parse_range(s).map(|range| inventory.fresh_ranges.push(range))
+            } else {
+                parse_id(s).map(|id| inventory.available_ids.push(id))
+            };
+            parsed.map_err(|e| e.at_line(index + 1))?;
+        }
+        Ok(inventory)
+    }
+}
+
+impl fmt::Display for Inventory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for range in &self.fresh_ranges {
+            writeln!(f, "{}-{}", range.start, range.end)?;
+        }
+        writeln!(f)?;
+        for id in &self.available_ids {
+            writeln!(f, "{id}")?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Day05;
+
+// The merged fresh ranges and available IDs as the parts read them.
 #[derive(Default)]
-pub struct Day05 {
+pub struct Parsed {
     fresh_ranges: Vec<(i64, i64)>,
     available_ids: Vec<i64>,
+    // Number of fresh IDs when the IDs were streamed instead of stored.
+    streamed_fresh: Option<usize>,
 }
 
-impl Day05 {
-    pub fn new() -> Self {
-        Self::default()
-    }
-
+impl Parsed {
     // Takes an ingredient ID, binary-searches merged fresh ranges, and returns whether the ID is fresh.
     fn is_fresh(&self, id: i64) -> bool {
         let mut lo = 0usize;
@@ -29,34 +93,12 @@ impl Day05 {
         }
         false
     }
-}
-
-impl Solution for Day05 {
-    // Takes fresh ranges and available IDs, parses both sections, and merges overlapping ranges.
-    fn set_input(&mut self, lines: &[String]) {
-        self.fresh_ranges.clear();
-        self.available_ids.clear();
 
-        let mut section = 0;
-
-        for line in lines {
-            let s = line.trim();
-            if s.is_empty() {
-                section += 1;
-                continue;
-            }
-
-            if section == 0 {
-                let mut parts = s.split('-');
-                let start: i64 = parts.next().unwrap().parse().unwrap();
-                let end: i64 = parts.next().unwrap().parse().unwrap();
-                self.fresh_ranges.push((start, end));
-            } else {
-                let id: i64 = s.parse().unwrap();
-                self.available_ids.push(id);
-            }
+    // Sorts the fresh ranges and merges overlapping ones in place.
+    fn merge_ranges(&mut self) {
+        if self.fresh_ranges.is_empty() {
+            return;
         }
-
         self.fresh_ranges.sort_by_key(|range| range.0);
 
         let mut merged: Vec<(i64, i64)> = Vec::new();
@@ -74,12 +116,116 @@ impl Solution for Day05 {
 
         self.fresh_ranges = merged;
     }
+}
+
+// Takes a "start-end" line and returns the inclusive range.
+fn parse_range(s: &[u8]) -> Result<FreshRange, ParseError> {
+    parse::parse_range(s)
+        .filter(|&(start, end)| start <= end && end <= i64::MAX as u64)
+        .map(|(start, end)| FreshRange {
+            start: start as i64,
+            end: end as i64,
+        })
+        .ok_or_else(|| {
+            ParseError::new(format!(
+                "fresh ranges must look like 3-5: {:?}",
+                String::from_utf8_lossy(s)
+            ))
+        })
+}
+
+// Takes an ingredient ID line and returns the ID.
+fn parse_id(s: &[u8]) -> Result<i64, ParseError> {
+    parse_u64(s)
+        .and_then(|id| i64::try_from(id).ok())
+        .ok_or_else(|| {
+            ParseError::new(format!(
+                "ingredient IDs must be numbers: {:?}",
+                String::from_utf8_lossy(s)
+            ))
+        })
+}
+
+impl Puzzle for Day05 {
+    type Input = Parsed;
+
+    const PARTS: &'static [Part<Self>] = &[
+        Part::new(
+            1,
+            "fresh-available",
+            "Available ingredient IDs that are fresh",
+            Self::part1,
+        ),
+        Part::new(
+            2,
+            "fresh-ids",
+            "Ingredient IDs the fresh ranges cover",
+            Self::part2,
+        ),
+    ];
+
+    // Expects the fresh ranges, a blank line, then the available IDs.
+    fn input_shape() -> InputShape {
+        InputShape::Sections(2..=2)
+    }
+
+    // Takes fresh ranges and available IDs, parses both sections, and returns them with overlapping ranges merged.
+    fn parse(input: &Input) -> Result<Parsed, ParseError> {
+        let inventory: Inventory = input.as_str().parse()?;
+        let mut parsed = Parsed {
+            fresh_ranges: inventory
+                .fresh_ranges
+                .iter()
+                .map(|range| (range.start, range.end))
+                .collect(),
+            available_ids: inventory.available_ids,
+            streamed_fresh: None,
+        };
+        parsed.merge_ranges();
+        Ok(parsed)
+    }
+
+    // Takes a reader over both sections, loads and merges the ranges, then checks each ID as it arrives without storing it.
+    fn stream(reader: &mut dyn BufRead) -> io::Result<Parsed> {
+        let mut parsed = Parsed::default();
+
+        let mut loading_ranges = true;
+        let mut fresh = 0;
+        for_each_line(reader, |_, line| {
+            let s = line.as_bytes().trim_ascii();
+            if loading_ranges {
+                if s.is_empty() {
+                    // Like Inventory::from_str, blank lines before the first range are skipped.
+                    if !parsed.fresh_ranges.is_empty() {
+                        parsed.merge_ranges();
+                        loading_ranges = false;
+                    }
+                } else {
+                    let range = parse_range(s)?;
+                    parsed.fresh_ranges.push((range.start, range.end));
+                }
+            } else if !s.is_empty() && parsed.is_fresh(parse_id(s)?) {
+                fresh += 1;
+            }
+            Ok(())
+        })?;
+        if loading_ranges {
+            parsed.merge_ranges();
+        }
+        parsed.streamed_fresh = Some(fresh);
+        Ok(parsed)
+    }
+}
 
+impl Day05 {
     // Checks all available IDs against fresh ranges and returns how many are fresh.
-    fn part1(&mut self) -> String {
+    fn part1(input: &Parsed) -> String {
+        if let Some(count) = input.streamed_fresh {
+            return count.to_string();
+        }
         let mut count = 0;
-        for &id in &self.available_ids {
-            if self.is_fresh(id) {
+        for &id in &input.available_ids {
+            if input.is_fresh(id) {
                 count += 1;
             }
         }
@@ -87,11 +233,12 @@ impl Solution for Day05 {
     }
 
     // Sums the sizes of all merged fresh ranges and returns the total number of fresh IDs.
-    fn part2(&mut self) -> String {
-        let total: i64 = self
+    fn part2(input: &Parsed) -> String {
+        // A range can span all of i64, so its length and the total need a wider type.
+        let total: u128 = input
             .fresh_ranges
             .iter()
-            .map(|(start, end)| end - start + 1)
+            .map(|&(start, end)| (end - start) as u128 + 1)
             .sum();
         total.to_string()
     }
@@ -100,28 +247,66 @@ impl Solution for Day05 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::days::Solution;
 
-    fn example_input() -> Vec<String> {
-        vec![
+    fn example_input() -> Input {
+        Input::from_lines(&[
             "3-5", "10-14", "16-20", "12-18", "", "1", "5", "8", "11", "17", "32",
-        ]
-        .into_iter()
-        .map(|s| s.to_string())
-        .collect()
+        ])
     }
 
     #[test]
     fn part1_example() {
-        let mut d = Day05::new();
-        d.set_input(&example_input());
-        assert_eq!(d.part1(), "3");
+        let input = Day05::parse(&example_input()).unwrap();
+        assert_eq!(Day05::part1(&input), "3");
     }
 
     #[test]
     fn part2_example() {
-        let mut d = Day05::new();
-        d.set_input(&example_input());
-        assert_eq!(d.part2(), "14");
+        let input = Day05::parse(&example_input()).unwrap();
+        assert_eq!(Day05::part2(&input), "14");
+    }
+
+    #[test]
+    fn input_model_round_trips() {
+        let inventory: Inventory = example_input().as_str().parse().unwrap();
+        assert_eq!(inventory.fresh_ranges[1], FreshRange { start: 10, end: 14 });
+        assert_eq!(inventory.available_ids, vec![1, 5, 8, 11, 17, 32]);
+        assert_eq!(inventory.to_string(), example_input().as_str());
+
+        let error = "3-5\n\n1\n2-4\n".parse::<Inventory>().unwrap_err();
+        assert_eq!(error.line(), Some(4));
+    }
+
+    #[test]
+    fn streamed_input_gives_the_same_answers() {
+        let streamed = Day05::stream(&mut example_input().as_bytes()).unwrap();
+        assert_eq!(
+            (Day05::part1(&streamed), Day05::part2(&streamed)),
+            ("3".to_string(), "14".to_string())
+        );
+    }
+
+    #[test]
+    fn counts_ranges_spanning_all_ids() {
+        let input = Day05::parse(&Input::from("0-9223372036854775807\n\n5\n")).unwrap();
+        assert_eq!(Day05::part2(&input), "9223372036854775808");
+    }
+
+    #[test]
+    fn streams_empty_input_and_a_leading_blank_line() {
+        let empty = Day05::stream(&mut "".as_bytes()).unwrap();
+        assert_eq!(
+            (Day05::part1(&empty), Day05::part2(&empty)),
+            ("0".to_string(), "0".to_string())
+        );
+
+        let text = format!("\n{}", example_input().as_str());
+        let streamed = Day05::stream(&mut text.as_bytes()).unwrap();
+        let parsed = Day05::parse(&Input::from(text.as_str())).unwrap();
+        assert_eq!(
+            (Day05::part1(&streamed), Day05::part2(&streamed)),
+            (Day05::part1(&parsed), Day05::part2(&parsed))
+        );
+        assert_eq!(Day05::part1(&streamed), "3");
     }
 }
@@ -1,29 +1,81 @@
-use crate::days::Solution;
+use crate::days::{Part, Puzzle};
+use crate::input::{self, Input, InputShape};
+use crate::parse::{find_other_byte, Grid, ParseError};
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+// The whole puzzle input: rows of numbers over a row of operators, padded with spaces to equal width.
+//
+// Problems are the column spans between all-blank columns, so alignment within a row matters and
+// the rows are kept as text.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Worksheet(#[serde(serialize_with = "Grid::serialize_text")] pub Grid);
+
+impl FromStr for Worksheet {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse_padded(input::byte_lines(text.as_bytes()), b' ');
+        const NUMBER_BYTES: [u8; 11] = *b" 0123456789";
+        for (index, row) in grid.row_iter().enumerate() {
+            let is_operator_row = index + 1 == grid.rows;
+            let other = if is_operator_row {
+                find_other_byte(row, [b' ', b'+', b'*'])
+            } else {
+                find_other_byte(row, NUMBER_BYTES)
+            };
+            if other.is_some() {
+                let expected = if is_operator_row {
+                    "'+', '*' and spaces"
+                } else {
+                    "digits and spaces"
+                };
+                return Err(
+                    ParseError::new(format!("worksheet row must hold only {expected}"))
+                        .at_line(index + 1),
+                );
+            }
+        }
+        Ok(Self(grid))
+    }
+}
+
+impl fmt::Display for Worksheet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write_rows(f, char::from)
+    }
+}
+
+pub struct Day06;
 
+// The worksheet grid and its problem columns as the parts read them.
 #[derive(Default)]
-pub struct Day06 {
-    grid: Vec<Vec<u8>>,
+pub struct Parsed {
+    // Rows padded with spaces to `cols` and stored back to back.
+    grid: Vec<u8>,
     spans: Vec<(usize, usize)>,
     rows: usize,
     cols: usize,
 }
 
-impl Day06 {
-    pub fn new() -> Self {
-        Self::default()
-    }
-
+impl Parsed {
     // -----------------------------------------------------------
     // Helpers
     // -----------------------------------------------------------
 
+    // Takes a row index and returns that row of the padded grid.
+    fn row(&self, r: usize) -> &[u8] {
+        &self.grid[r * self.cols..(r + 1) * self.cols]
+    }
+
     // Scans columns for blank separators, groups each worksheet problem span, and returns inclusive column bounds.
     fn find_problem_spans(&self) -> Vec<(usize, usize)> {
         let mut is_blank = vec![true; self.cols];
 
         for (c, blank) in is_blank.iter_mut().enumerate() {
             for r in 0..self.rows {
-                if self.grid[r][c] != b' ' {
+                if self.grid[r * self.cols + c] != b' ' {
                     *blank = false;
                     break;
                 }
@@ -56,7 +108,7 @@ impl Day06 {
     // Takes a problem span, finds its bottom-row operator, and returns '+' or '*'.
     fn get_operator(&self, span: (usize, usize)) -> u8 {
         let (start, end) = span;
-        let row = &self.grid[self.rows - 1][start..=end];
+        let row = &self.row(self.rows - 1)[start..=end];
         for &ch in row {
             if ch == b'+' || ch == b'*' {
                 return ch;
@@ -71,7 +123,7 @@ impl Day06 {
         for &span @ (start, end) in &self.spans {
             let operator = self.get_operator(span);
             let mut value = if operator == b'+' { 0 } else { 1 };
-            for row in &self.grid[..self.rows - 1] {
+            for row in (0..self.rows - 1).map(|r| self.row(r)) {
                 let number = row[start..=end]
                     .iter()
                     .filter(|&&byte| byte != b' ')
@@ -95,7 +147,7 @@ impl Day06 {
             let mut value = if operator == b'+' { 0 } else { 1 };
             for col in start..=end {
                 let mut number = 0i64;
-                for row in &self.grid[..self.rows - 1] {
+                for row in (0..self.rows - 1).map(|r| self.row(r)) {
                     let byte = row[col];
                     if byte != b' ' {
                         number = number * 10 + i64::from(byte - b'0');
@@ -113,67 +165,90 @@ impl Day06 {
     }
 }
 
-impl Solution for Day06 {
-    // Takes the worksheet rows, pads them to equal width, and stores the normalized grid.
-    fn set_input(&mut self, lines: &[String]) {
-        self.grid.clear();
-
-        let mut max_cols = 0;
-        for line in lines {
-            max_cols = max_cols.max(line.len());
-            self.grid.push(line.as_bytes().to_vec());
-        }
-
-        for row in &mut self.grid {
-            if row.len() < max_cols {
-                row.resize(max_cols, b' ');
-            }
-        }
+impl Puzzle for Day06 {
+    type Input = Parsed;
+
+    const PARTS: &'static [Part<Self>] = &[
+        Part::new(
+            1,
+            "row-problems",
+            "Grand total of the worksheet read in rows",
+            Self::part1,
+        ),
+        Part::new(
+            2,
+            "column-problems",
+            "Grand total of the worksheet read in columns, right to left",
+            Self::part2,
+        ),
+    ];
+
+    // Expects the worksheet rows; shorter rows are padded with spaces.
+    fn input_shape() -> InputShape {
+        InputShape::Grid { ragged: true }
+    }
 
-        self.rows = self.grid.len();
-        self.cols = max_cols;
-        self.spans = self.find_problem_spans();
+    // Takes the worksheet rows, padded to equal width, and returns the grid with its problem columns.
+    fn parse(input: &Input) -> Result<Parsed, ParseError> {
+        let Worksheet(grid) = input.as_str().parse()?;
+        let mut parsed = Parsed {
+            grid: grid.cells,
+            rows: grid.rows,
+            cols: grid.cols,
+            spans: Vec::new(),
+        };
+        parsed.spans = parsed.find_problem_spans();
+        Ok(parsed)
     }
+}
 
+impl Day06 {
     // Evaluates row-oriented worksheet problems and returns their grand total.
-    fn part1(&mut self) -> String {
-        self.evaluate_rows().to_string()
+    fn part1(input: &Parsed) -> String {
+        input.evaluate_rows().to_string()
     }
 
     // Evaluates column-oriented worksheet problems and returns their grand total.
-    fn part2(&mut self) -> String {
-        self.evaluate_columns().to_string()
+    fn part2(input: &Parsed) -> String {
+        input.evaluate_columns().to_string()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::days::Solution;
 
-    fn example_input() -> Vec<String> {
-        vec![
+    fn example_input() -> Input {
+        Input::from_lines(&[
             "123 328  51 64 ",
             " 45 64  387 23 ",
             "  6 98  215 314",
             "*   +   *   +  ",
-        ]
-        .into_iter()
-        .map(|s| s.to_string())
-        .collect()
+        ])
     }
 
     #[test]
     fn part1_example() {
-        let mut d = Day06::new();
-        d.set_input(&example_input());
-        assert_eq!(d.part1(), "4277556");
+        let input = Day06::parse(&example_input()).unwrap();
+        assert_eq!(Day06::part1(&input), "4277556");
     }
 
     #[test]
     fn part2_example() {
-        let mut d = Day06::new();
-        d.set_input(&example_input());
-        assert_eq!(d.part2(), "3263827");
+        let input = Day06::parse(&example_input()).unwrap();
+        assert_eq!(Day06::part2(&input), "3263827");
+    }
+
+    #[test]
+    fn input_model_round_trips() {
+        let worksheet: Worksheet = example_input().as_str().parse().unwrap();
+        assert_eq!((worksheet.0.rows, worksheet.0.cols), (4, 15));
+        assert_eq!(worksheet.to_string(), example_input().as_str());
+
+        let ragged: Worksheet = "12 3\n4\n+  *\n".parse().unwrap();
+        assert_eq!(ragged.to_string(), "12 3\n4   \n+  *\n");
+
+        let error = "12\n4x\n+ \n".parse::<Worksheet>().unwrap_err();
+        assert_eq!(error.line(), Some(2));
     }
 }
@@ -1,65 +1,110 @@
-use crate::days::Solution;
+use crate::days::{Part, Puzzle};
+use crate::input::{self, Input, InputShape};
+use crate::parse::{find_other_byte, Grid, ParseError};
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+// The whole puzzle input: the manifold diagram with the start 'S' in its first row, splitters '^'
+// and empty space '.'. Shorter rows are padded with '.'.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Manifold(#[serde(serialize_with = "Grid::serialize_text")] pub Grid);
+
+impl Manifold {
+    // Returns the column of the start 'S' in the first row.
+    pub fn start_col(&self) -> Option<usize> {
+        self.0.row_iter().next()?.iter().position(|&c| c == b'S')
+    }
+}
 
+impl FromStr for Manifold {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, ParseError> {
+        let manifold = Self(Grid::parse_padded(input::byte_lines(text.as_bytes()), b'.'));
+        if let Some(other) = find_other_byte(&manifold.0.cells, [b'.', b'^', b'S']) {
+            return Err(ParseError::new(format!(
+                "the manifold may only contain '.', '^' and 'S', found {:?}",
+                char::from(other)
+            )));
+        }
+        if manifold.start_col().is_none() {
+            return Err(ParseError::new("start position S not found in the first row").at_line(1));
+        }
+        Ok(manifold)
+    }
+}
+
+impl fmt::Display for Manifold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write_rows(f, char::from)
+    }
+}
+
+pub struct Day07;
+
+// The manifold as the parts read it.
 #[derive(Default)]
-pub struct Day07 {
-    manifold: Vec<Vec<u8>>,
+pub struct Parsed {
+    // Rows padded with '.' to `cols` and stored back to back.
+    manifold: Vec<u8>,
     rows: usize,
     cols: usize,
     start_col: usize,
 }
 
-impl Day07 {
-    pub fn new() -> Self {
-        Self::default()
-    }
-}
-
-impl Solution for Day07 {
-    // Takes the manifold diagram, pads rows to equal width, and records the starting beam column.
-    fn set_input(&mut self, lines: &[String]) {
-        self.manifold.clear();
+impl Parsed {}
 
-        // Copy lines exactly
-        let mut max_cols = 0;
-        for line in lines {
-            max_cols = max_cols.max(line.len());
-            self.manifold.push(line.as_bytes().to_vec());
-        }
+impl Puzzle for Day07 {
+    type Input = Parsed;
 
-        // Normalize width with spaces
-        for row in &mut self.manifold {
-            if row.len() < max_cols {
-                row.resize(max_cols, b' ');
-            }
-        }
+    const PARTS: &'static [Part<Self>] = &[
+        Part::new(1, "splits", "Times the tachyon beam is split", Self::part1),
+        Part::new(
+            2,
+            "timelines",
+            "Timelines a single tachyon particle ends up on",
+            Self::part2,
+        ),
+    ];
 
-        self.rows = self.manifold.len();
-        self.cols = max_cols;
+    // Expects the manifold rows; shorter rows are padded with '.'.
+    fn input_shape() -> InputShape {
+        InputShape::Grid { ragged: true }
+    }
 
-        // Locate 'S' in first row
-        self.start_col = self.manifold[0]
-            .iter()
-            .position(|&c| c == b'S')
-            .expect("Start position S not found");
+    // Takes the manifold diagram and returns its rows with the starting beam column.
+    fn parse(input: &Input) -> Result<Parsed, ParseError> {
+        let manifold: Manifold = input.as_str().parse()?;
+        let start_col = manifold.start_col().unwrap();
+        let Manifold(grid) = manifold;
+        Ok(Parsed {
+            manifold: grid.cells,
+            rows: grid.rows,
+            cols: grid.cols,
+            start_col,
+        })
     }
+}
 
+impl Day07 {
     // Propagates reachable beam columns through the manifold and returns the number of splitter hits.
-    fn part1(&mut self) -> String {
-        let mut buf_a = vec![false; self.cols];
-        let mut buf_b = vec![false; self.cols];
+    fn part1(input: &Parsed) -> String {
+        let mut buf_a = vec![false; input.cols];
+        let mut buf_b = vec![false; input.cols];
 
         let mut active_beams = &mut buf_a;
         let mut next_beams = &mut buf_b;
 
-        active_beams[self.start_col] = true;
+        active_beams[input.start_col] = true;
 
         let mut split_count = 0;
 
-        for r in 1..self.rows {
-            let row = &self.manifold[r];
+        for r in 1..input.rows {
+            let row = &input.manifold[r * input.cols..(r + 1) * input.cols];
             next_beams.fill(false);
 
-            for c in 0..self.cols {
+            for c in 0..input.cols {
                 if !active_beams[c] {
                     continue;
                 }
@@ -69,7 +114,7 @@ impl Solution for Day07 {
                     if c > 0 {
                         next_beams[c - 1] = true;
                     }
-                    if c + 1 < self.cols {
+                    if c + 1 < input.cols {
                         next_beams[c + 1] = true;
                     }
                 } else {
@@ -84,20 +129,20 @@ impl Solution for Day07 {
     }
 
     // Propagates beam path counts through splitters and returns the number of exiting beam timelines.
-    fn part2(&mut self) -> String {
-        let mut buf_a = vec![0i64; self.cols];
-        let mut buf_b = vec![0i64; self.cols];
+    fn part2(input: &Parsed) -> String {
+        let mut buf_a = vec![0i64; input.cols];
+        let mut buf_b = vec![0i64; input.cols];
 
         let mut beam_counts = &mut buf_a;
         let mut next_counts = &mut buf_b;
 
-        beam_counts[self.start_col] = 1;
+        beam_counts[input.start_col] = 1;
 
-        for r in 1..self.rows {
-            let row = &self.manifold[r];
+        for r in 1..input.rows {
+            let row = &input.manifold[r * input.cols..(r + 1) * input.cols];
             next_counts.fill(0);
 
-            for c in 0..self.cols {
+            for c in 0..input.cols {
                 let count = beam_counts[c];
                 if count == 0 {
                     continue;
@@ -107,7 +152,7 @@ impl Solution for Day07 {
                     if c > 0 {
                         next_counts[c - 1] += count;
                     }
-                    if c + 1 < self.cols {
+                    if c + 1 < input.cols {
                         next_counts[c + 1] += count;
                     }
                 } else {
@@ -126,10 +171,9 @@ impl Solution for Day07 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::days::Solution;
 
-    fn example_input() -> Vec<String> {
-        vec![
+    fn example_input() -> Input {
+        Input::from_lines(&[
             ".......S.......",
             "...............",
             ".......^.......",
@@ -146,23 +190,29 @@ mod tests {
             "...............",
             ".^.^.^.^.^...^.",
             "...............",
-        ]
-        .into_iter()
-        .map(|s| s.to_string())
-        .collect()
+        ])
     }
 
     #[test]
     fn part1_example() {
-        let mut d = Day07::new();
-        d.set_input(&example_input());
-        assert_eq!(d.part1(), "21");
+        let input = Day07::parse(&example_input()).unwrap();
+        assert_eq!(Day07::part1(&input), "21");
     }
 
     #[test]
     fn part2_example() {
-        let mut d = Day07::new();
-        d.set_input(&example_input());
-        assert_eq!(d.part2(), "40");
+        let input = Day07::parse(&example_input()).unwrap();
+        assert_eq!(Day07::part2(&input), "40");
+    }
+
+    #[test]
+    fn input_model_round_trips() {
+        let manifold: Manifold = example_input().as_str().parse().unwrap();
+        assert_eq!((manifold.0.rows, manifold.0.cols), (16, 15));
+        assert_eq!(manifold.start_col(), Some(7));
+        assert_eq!(manifold.to_string(), example_input().as_str());
+
+        assert!("...\n.S.\n".parse::<Manifold>().is_err());
+        assert!(".S.\n.#.\n".parse::<Manifold>().is_err());
     }
 }
@@ -1,34 +1,57 @@
-use crate::days::Solution;
+use crate::days::{Part, Puzzle};
+use crate::input::{Input, InputShape};
+use crate::parse::{parse_i64_array, Lines, ParseError};
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
 
 // -----------------------------------------------------------
 // Data types
 // -----------------------------------------------------------
 
-#[derive(Clone, Copy)]
-struct Point3 {
-    x: i64,
-    y: i64,
-    z: i64,
+// One junction box position, written "X,Y,Z".
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
 }
 
-#[derive(Clone, Copy)]
-struct Edge {
-    distance_sq: i64,
-    a: usize,
-    b: usize,
+// A possible connection between junctions `a` and `b` (indices into the input), keyed by squared length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Edge {
+    pub distance_sq: i64,
+    pub a: usize,
+    pub b: usize,
 }
 
+// The whole puzzle input: one junction position per line.
+pub type Junctions = Lines<Point3>;
+
+impl FromStr for Point3 {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, ParseError> {
+        parse_point3(line.as_bytes().trim_ascii())
+    }
+}
+
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+pub struct Day08;
+
+// The junction boxes and their sorted connections as the parts read them.
 #[derive(Default)]
-pub struct Day08 {
+pub struct Parsed {
     junctions: Vec<Point3>,
     edges: Vec<Edge>,
 }
 
-impl Day08 {
-    pub fn new() -> Self {
-        Self::default()
-    }
-
+impl Parsed {
     // -----------------------------------------------------------
     // Distance helpers
     // -----------------------------------------------------------
@@ -230,35 +253,55 @@ impl Dsu {
 // -----------------------------------------------------------
 
 // Takes an X,Y,Z input line, parses each coordinate, and returns a 3D point.
-fn parse_point3(line: &str) -> Point3 {
-    let mut it = line.split(',');
-    Point3 {
-        x: it.next().unwrap().parse().unwrap(),
-        y: it.next().unwrap().parse().unwrap(),
-        z: it.next().unwrap().parse().unwrap(),
-    }
+fn parse_point3(line: &[u8]) -> Result<Point3, ParseError> {
+    let [x, y, z] = parse_i64_array(line, b',').ok_or_else(|| {
+        ParseError::new(format!(
+            "junctions must look like 162,817,812: {:?}",
+            String::from_utf8_lossy(line)
+        ))
+    })?;
+    Ok(Point3 { x, y, z })
 }
 
 // -----------------------------------------------------------
 // Solution impl
 // -----------------------------------------------------------
 
-impl Solution for Day08 {
-    // Takes junction coordinate lines, parses them, and precomputes sorted connection edges.
-    fn set_input(&mut self, lines: &[String]) {
-        self.junctions.clear();
-        for line in lines {
-            let s = line.trim();
-            if !s.is_empty() {
-                self.junctions.push(parse_point3(s));
-            }
-        }
-        self.edges = Self::build_sorted_edges(&self.junctions);
+impl Puzzle for Day08 {
+    type Input = Parsed;
+
+    const PARTS: &'static [Part<Self>] = &[
+        Part::new(
+            1,
+            "largest-circuits",
+            "Product of the three largest circuits after the 1000 closest connections",
+            Self::part1,
+        ),
+        Part::new(
+            2,
+            "last-connection",
+            "Product of the X coordinates of the last two junctions to join one circuit",
+            Self::part2,
+        ),
+    ];
+
+    // Expects one junction box per line.
+    fn input_shape() -> InputShape {
+        InputShape::Lines
     }
 
+    // Takes junction coordinate lines and returns them with the connection edges sorted by length.
+    fn parse(input: &Input) -> Result<Parsed, ParseError> {
+        let Lines(junctions) = input.as_str().parse()?;
+        let edges = Parsed::build_sorted_edges(&junctions);
+        Ok(Parsed { junctions, edges })
+    }
+}
+
+impl Day08 {
     // Connects the 1000 closest pairs and returns the product of the three largest circuit sizes.
-    fn part1(&mut self) -> String {
-        let sizes = Self::circuit_sizes_after_connections(&self.junctions, &self.edges, 1000);
+    fn part1(input: &Parsed) -> String {
+        let sizes = Parsed::circuit_sizes_after_connections(&input.junctions, &input.edges, 1000);
         if sizes.len() < 3 {
             return "0".to_string();
         }
@@ -266,12 +309,12 @@ impl Solution for Day08 {
     }
 
     // Connects until all junctions share one circuit and returns the puzzle's final endpoint product.
-    fn part2(&mut self) -> String {
-        if self.junctions.len() < 2 {
+    fn part2(input: &Parsed) -> String {
+        if input.junctions.len() < 2 {
             return "0".to_string();
         }
-        let (a, b) = Self::final_connection(&self.junctions, &self.edges);
-        (self.junctions[a].x * self.junctions[b].x).to_string()
+        let (a, b) = Parsed::final_connection(&input.junctions, &input.edges);
+        (input.junctions[a].x * input.junctions[b].x).to_string()
     }
 }
 
@@ -283,8 +326,8 @@ impl Solution for Day08 {
 mod tests {
     use super::*;
 
-    fn example_input() -> Vec<String> {
-        vec![
+    fn example_input() -> Input {
+        Input::from_lines(&[
             "162,817,812",
             "57,618,57",
             "906,360,560",
@@ -305,18 +348,14 @@ mod tests {
             "862,61,35",
             "984,92,344",
             "425,690,689",
-        ]
-        .into_iter()
-        .map(|s| s.to_string())
-        .collect()
+        ])
     }
 
     #[test]
     fn part1_example() {
-        let mut d = Day08::new();
-        d.set_input(&example_input());
+        let d = Day08::parse(&example_input()).unwrap();
 
-        let sizes = Day08::circuit_sizes_after_connections(&d.junctions, &d.edges, 10);
+        let sizes = Parsed::circuit_sizes_after_connections(&d.junctions, &d.edges, 10);
         let got = sizes[0] * sizes[1] * sizes[2];
         let want = 40;
 
@@ -325,16 +364,32 @@ mod tests {
 
     #[test]
     fn part2_example() {
-        let mut d = Day08::new();
-        d.set_input(&example_input());
+        let d = Day08::parse(&example_input()).unwrap();
 
-        let (a, b) = Day08::final_connection(&d.junctions, &d.edges);
+        let (a, b) = Parsed::final_connection(&d.junctions, &d.edges);
         let got = d.junctions[a].x * d.junctions[b].x;
         let want: i64 = 25272;
 
         assert_eq!(got, want);
     }
 
+    #[test]
+    fn input_model_round_trips() {
+        let junctions: Junctions = example_input().as_str().parse().unwrap();
+        assert_eq!(junctions.0.len(), 20);
+        assert_eq!(
+            junctions.0[1],
+            Point3 {
+                x: 57,
+                y: 618,
+                z: 57
+            }
+        );
+        assert_eq!(junctions.to_string(), example_input().as_str());
+
+        assert!("1,2,3\n1,2\n".parse::<Junctions>().is_err());
+    }
+
     #[test]
     fn radix_sort_handles_each_required_pass_count() {
         let keys = [
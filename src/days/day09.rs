@@ -1,34 +1,59 @@
-use crate::days::Solution;
+use crate::days::{Part, Puzzle};
+use crate::input::{Input, InputShape};
+use crate::parse::{parse_i64_array, Lines, ParseError};
 use rayon::prelude::*;
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
 use std::sync::atomic::{AtomicI64, Ordering};
 
-#[derive(Clone, Copy)]
-struct Point {
-    x: i32,
-    y: i32,
+// One red tile position, written "X,Y".
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
 }
 
-#[derive(Clone, Copy)]
-struct Segment {
-    x1: i32,
-    y1: i32,
-    x2: i32,
-    y2: i32,
+// An axis-aligned piece of the polygon boundary, with x1 <= x2 and y1 <= y2.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Segment {
+    pub x1: i32,
+    pub y1: i32,
+    pub x2: i32,
+    pub y2: i32,
 }
 
+// The whole puzzle input: the red tiles in boundary order, one per line.
+pub type RedTiles = Lines<Point>;
+
+impl FromStr for Point {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, ParseError> {
+        let [x, y] = parse_i64_array(line.as_bytes().trim_ascii(), b',')
+            .and_then(|[x, y]| Some([i32::try_from(x).ok()?, i32::try_from(y).ok()?]))
+            .ok_or_else(|| ParseError::new(format!("red tiles must look like 7,1: {line:?}")))?;
+        Ok(Self { x, y })
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+pub struct Day09;
+
+// The red tiles and the polygon edges between them as the parts read them.
 #[derive(Default)]
-pub struct Day09 {
+pub struct Parsed {
     red_tiles: Vec<Point>,
-    boundary: Vec<Segment>,
     horizontal_edges: Vec<Segment>,
     vertical_edges: Vec<Segment>,
 }
 
-impl Day09 {
-    pub fn new() -> Self {
-        Self::default()
-    }
-
+impl Parsed {
     // ----------------------------------------------------------
     // Part 1
     // ----------------------------------------------------------
@@ -55,9 +80,6 @@ impl Day09 {
     // Converts the ordered red-tile boundary into horizontal and vertical segments used by containment checks.
     fn build_edges(&mut self) {
         let point_count = self.red_tiles.len();
-        self.boundary.clear();
-        self.horizontal_edges.clear();
-        self.vertical_edges.clear();
 
         for i in 0..point_count {
             let a = self.red_tiles[i];
@@ -71,7 +93,6 @@ impl Day09 {
                     x2,
                     y2: a.y,
                 };
-                self.boundary.push(edge);
                 self.horizontal_edges.push(edge);
             } else {
                 let (y1, y2) = if a.y <= b.y { (a.y, b.y) } else { (b.y, a.y) };
@@ -81,7 +102,6 @@ impl Day09 {
                     x2: a.x,
                     y2,
                 };
-                self.boundary.push(edge);
                 self.vertical_edges.push(edge);
             }
         }
@@ -145,48 +165,59 @@ impl Day09 {
     }
 }
 
-impl Solution for Day09 {
-    // Takes coordinate lines, parses the ordered red tiles, and clears derived polygon edges.
-    fn set_input(&mut self, lines: &[String]) {
-        self.red_tiles.clear();
-        self.boundary.clear();
-        self.horizontal_edges.clear();
-        self.vertical_edges.clear();
-
-        for line in lines {
-            let line = line.trim();
-            if line.is_empty() {
-                continue;
-            }
-            let mut parts = line.split(',');
-            let x: i32 = parts.next().unwrap().parse().unwrap();
-            let y: i32 = parts.next().unwrap().parse().unwrap();
-            self.red_tiles.push(Point { x, y });
-        }
+impl Puzzle for Day09 {
+    type Input = Parsed;
+
+    const PARTS: &'static [Part<Self>] = &[
+        Part::new(
+            1,
+            "largest-rectangle",
+            "Largest rectangle with red tiles at two opposite corners",
+            Self::part1,
+        ),
+        Part::new(
+            2,
+            "largest-inside",
+            "Largest such rectangle using only red and green tiles",
+            Self::part2,
+        ),
+    ];
+
+    // Expects one red tile per line.
+    fn input_shape() -> InputShape {
+        InputShape::Lines
     }
 
+    // Takes coordinate lines and returns the ordered red tiles with the polygon edges between them.
+    fn parse(input: &Input) -> Result<Parsed, ParseError> {
+        let Lines(red_tiles) = input.as_str().parse()?;
+        let mut parsed = Parsed {
+            red_tiles,
+            ..Parsed::default()
+        };
+        parsed.build_edges();
+        Ok(parsed)
+    }
+}
+
+impl Day09 {
     // Finds the largest rectangle from any red-tile corner pair and returns its area.
-    fn part1(&mut self) -> String {
-        Self::max_area_inclusive(&self.red_tiles).to_string()
+    fn part1(input: &Parsed) -> String {
+        Parsed::max_area_inclusive(&input.red_tiles).to_string()
     }
 
     // Finds the largest rectangle contained in the boundary polygon and returns its area.
-    fn part2(&mut self) -> String {
-        if self.red_tiles.len() < 2 {
+    fn part2(input: &Parsed) -> String {
+        if input.red_tiles.len() < 2 {
             return "0".to_string();
         }
 
-        if self.boundary.is_empty() {
-            self.build_edges();
-        }
-
-        let point_count = self.red_tiles.len();
+        let point_count = input.red_tiles.len();
         let best = AtomicI64::new(0);
-        let solver = &*self;
 
         (0..point_count).into_par_iter().for_each(|i| {
-            let a = solver.red_tiles[i];
-            for &b in &solver.red_tiles[i + 1..] {
+            let a = input.red_tiles[i];
+            for &b in &input.red_tiles[i + 1..] {
                 let x1 = a.x.min(b.x);
                 let x2 = a.x.max(b.x);
                 let y1 = a.y.min(b.y);
@@ -197,15 +228,15 @@ impl Solution for Day09 {
                     continue;
                 }
 
-                if solver.rectangle_cut_by_polygon(x1, y1, x2, y2) {
+                if input.rectangle_cut_by_polygon(x1, y1, x2, y2) {
                     continue;
                 }
 
                 let opposite_corner_a = Point { x: x1, y: y2 };
                 let opposite_corner_b = Point { x: x2, y: y1 };
 
-                if !solver.point_inside_or_on(opposite_corner_a)
-                    || !solver.point_inside_or_on(opposite_corner_b)
+                if !input.point_inside_or_on(opposite_corner_a)
+                    || !input.point_inside_or_on(opposite_corner_b)
                 {
                     continue;
                 }
@@ -221,26 +252,29 @@ impl Solution for Day09 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::days::Solution;
 
-    fn example_input() -> Vec<String> {
-        vec!["7,1", "11,1", "11,7", "9,7", "9,5", "2,5", "2,3", "7,3"]
-            .into_iter()
-            .map(|s| s.to_string())
-            .collect()
+    fn example_input() -> Input {
+        Input::from_lines(&["7,1", "11,1", "11,7", "9,7", "9,5", "2,5", "2,3", "7,3"])
     }
 
     #[test]
     fn part1_example() {
-        let mut d = Day09::new();
-        d.set_input(&example_input());
-        assert_eq!(d.part1(), "50");
+        let input = Day09::parse(&example_input()).unwrap();
+        assert_eq!(Day09::part1(&input), "50");
     }
 
     #[test]
     fn part2_example() {
-        let mut d = Day09::new();
-        d.set_input(&example_input());
-        assert_eq!(d.part2(), "24");
+        let input = Day09::parse(&example_input()).unwrap();
+        assert_eq!(Day09::part2(&input), "24");
+    }
+
+    #[test]
+    fn input_model_round_trips() {
+        let tiles: RedTiles = example_input().as_str().parse().unwrap();
+        assert_eq!(tiles.0[1], Point { x: 11, y: 1 });
+        assert_eq!(tiles.to_string(), example_input().as_str());
+
+        assert!("7,1\n7,1,2\n".parse::<RedTiles>().is_err());
     }
 }
@@ -1,24 +1,33 @@
-use crate::days::Solution;
+use crate::days::{Part, Puzzle, Variant};
+use crate::input::{Input, InputShape};
+use crate::parse::{Lines, ParseError};
 use rayon::prelude::*;
+use serde::Serialize;
 use std::f64;
+use std::fmt;
+use std::str::FromStr;
 
-#[derive(Clone)]
+// One machine line: "[.##.] (3) (1,3) {3,5,4,7}" is the light pattern, the lights each button
+// toggles, and the joltage targets.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub struct Machine {
     pub target_lights: Vec<i32>,
     pub target_joltage: Vec<i32>,
     pub buttons: Vec<Vec<usize>>,
 }
 
+// The whole puzzle input: one machine per line.
+pub type Machines = Lines<Machine>;
+
+pub struct Day10;
+
+// The machines as the parts read them.
 #[derive(Default)]
-pub struct Day10 {
+pub struct Parsed {
     pub machines: Vec<Machine>,
 }
 
-impl Day10 {
-    pub fn new() -> Self {
-        Self::default()
-    }
-
+impl Parsed {
     // ------------------------------------------------------------
     // Part 1: GF(2) solve using bitsets (optimized)
     // ------------------------------------------------------------
@@ -311,80 +320,264 @@ impl Day10 {
         );
         best
     }
+
+    // ------------------------------------------------------------
+    // Part 2 reference: exhaustive search
+    // ------------------------------------------------------------
+    // Tries every press count for each button, widest buttons and largest counts first, and returns the fewest
+    // presses; exponential in the number of buttons. It skips counts that overshoot a target, fixes the count of
+    // a light's last button to what that light still needs, and stops once the remaining deficits cannot beat
+    // the best so far.
+    fn brute_joltage_presses(machine: &Machine) -> i64 {
+        struct Search<'a> {
+            target: &'a [i32],
+            buttons: Vec<&'a [usize]>,
+            // The last button (in search order) wired to each light.
+            last_button: Vec<usize>,
+            // The most lights any button from this index on is wired to.
+            widest_from: Vec<i32>,
+        }
+
+        impl Search<'_> {
+            fn dfs(&self, button: usize, values: &mut [i32], presses: i64, best: &mut i64) {
+                let deficits = self.target.iter().zip(values.iter()).map(|(t, v)| t - v);
+                let (largest, total) =
+                    deficits.fold((0, 0), |(largest, total), d| (largest.max(d), total + d));
+                if button == self.buttons.len() {
+                    if total == 0 && presses < *best {
+                        *best = presses;
+                    }
+                    return;
+                }
+                let width = self.widest_from[button].max(1);
+                let needed = largest.max((total + width - 1) / width);
+                if presses + i64::from(needed) >= *best {
+                    return;
+                }
+
+                let wired = self.buttons[button];
+                let deficit = |light: usize| self.target[light] - values[light];
+                let bound = wired.iter().map(|&light| deficit(light)).min().unwrap_or(0);
+                let mut forced = wired
+                    .iter()
+                    .filter(|&&light| self.last_button[light] == button)
+                    .map(|&light| deficit(light));
+                let counts = match forced.next() {
+                    Some(count) if count <= bound && forced.all(|other| other == count) => {
+                        count..=count
+                    }
+                    Some(_) => return,
+                    None => 0..=bound,
+                };
+
+                for count in counts.rev() {
+                    for &light in wired {
+                        values[light] += count;
+                    }
+                    self.dfs(button + 1, values, presses + i64::from(count), best);
+                    for &light in wired {
+                        values[light] -= count;
+                    }
+                }
+            }
+        }
+
+        let mut buttons: Vec<&[usize]> = machine.buttons.iter().map(Vec::as_slice).collect();
+        buttons.sort_by_key(|wired| std::cmp::Reverse(wired.len()));
+        let mut last_button = vec![usize::MAX; machine.target_joltage.len()];
+        for (index, wired) in buttons.iter().enumerate() {
+            for &light in *wired {
+                last_button[light] = index;
+            }
+        }
+        let mut widest_from = vec![0; buttons.len() + 1];
+        for index in (0..buttons.len()).rev() {
+            widest_from[index] = widest_from[index + 1].max(buttons[index].len() as i32);
+        }
+
+        let search = Search {
+            target: &machine.target_joltage,
+            buttons,
+            last_button,
+            widest_from,
+        };
+        let mut best = i64::MAX;
+        let mut values = vec![0; machine.target_joltage.len()];
+        search.dfs(0, &mut values, 0, &mut best);
+        best
+    }
 }
 
 // ------------------------------------------------------------
 // Parsing
 // ------------------------------------------------------------
-// Takes a parenthesized/comma-separated list, parses indices, and returns the button wiring targets.
-fn parse_list(s: &str) -> Vec<usize> {
-    s.trim_matches(|c| c == '(' || c == ')' || c == '{' || c == '}')
+// Takes a bracketed comma-separated list such as "(1,3)", checks its delimiters, and returns the numbers.
+fn parse_list<T: FromStr>(token: &str, open: char, close: char) -> Result<Vec<T>, ParseError> {
+    let invalid = || ParseError::new(format!("expected a list like {open}1,3{close}: {token:?}"));
+    let inner = token
+        .strip_prefix(open)
+        .and_then(|rest| rest.strip_suffix(close))
+        .ok_or_else(invalid)?;
+    if inner.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    inner
         .split(',')
-        .filter_map(|x| x.trim().parse::<usize>().ok())
+        .map(|number| number.trim().parse().map_err(|_| invalid()))
         .collect()
 }
 
-// Takes one machine description line, parses lights, buttons, and joltage targets, and returns a Machine.
-fn parse_machine(line: &str) -> Machine {
-    let mut parts = line.split_whitespace();
-
-    let lights_str = parts.next().unwrap();
-    let target_lights = lights_str
-        .trim_matches(&['[', ']'][..])
-        .chars()
-        .map(|c| if c == '#' { 1 } else { 0 })
-        .collect::<Vec<_>>();
-
-    let mut buttons = Vec::new();
-    let mut target_joltage = Vec::new();
-
-    for p in parts {
-        if p.starts_with('(') {
-            buttons.push(parse_list(p));
-        } else if p.starts_with('{') {
-            target_joltage = p
-                .trim_matches(&['{', '}'][..])
-                .split(',')
-                .filter_map(|x| x.trim().parse::<i32>().ok())
-                .collect();
+// Takes a formatter, a list and its delimiters, and writes the list like "(1,3)".
+fn write_list<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    items: &[T],
+    open: char,
+    close: char,
+) -> fmt::Result {
+    write!(f, "{open}")?;
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            f.write_str(",")?;
+        }
+        write!(f, "{item}")?;
+    }
+    write!(f, "{close}")
+}
+
+impl FromStr for Machine {
+    type Err = ParseError;
+
+    // Takes one machine description line and parses lights, buttons, and joltage targets.
+    fn from_str(line: &str) -> Result<Self, ParseError> {
+        let mut parts = line.split_whitespace();
+
+        let lights = parts
+            .next()
+            .and_then(|lights| lights.strip_prefix('[')?.strip_suffix(']'))
+            .filter(|lights| lights.bytes().all(|c| c == b'#' || c == b'.'))
+            .ok_or_else(|| {
+                ParseError::new("machines must start with a light pattern like [.##.]")
+            })?;
+        let target_lights = lights.bytes().map(|c| i32::from(c == b'#')).collect();
+
+        let mut buttons = Vec::new();
+        let mut target_joltage = None;
+        for part in parts {
+            if target_joltage.is_some() {
+                return Err(ParseError::new("the joltage targets must come last"));
+            }
+            if part.starts_with('{') {
+                target_joltage = Some(parse_list(part, '{', '}')?);
+            } else {
+                buttons.push(parse_list(part, '(', ')')?);
+            }
         }
+
+        let target_joltage: Vec<i32> = target_joltage
+            .ok_or_else(|| ParseError::new("machines must end with joltage targets like {3,5}"))?;
+        let light_count = lights.len().min(target_joltage.len());
+        if let Some(light) = buttons
+            .iter()
+            .flatten()
+            .find(|&&light| light >= light_count)
+        {
+            return Err(ParseError::new(format!(
+                "a button wires light {light}, but the machine has only {light_count} lights"
+            )));
+        }
+
+        Ok(Machine {
+            target_lights,
+            target_joltage,
+            buttons,
+        })
     }
+}
 
-    Machine {
-        target_lights,
-        target_joltage,
-        buttons,
+impl fmt::Display for Machine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lights: String = self
+            .target_lights
+            .iter()
+            .map(|&on| if on != 0 { '#' } else { '.' })
+            .collect();
+        write!(f, "[{lights}]")?;
+        for button in &self.buttons {
+            f.write_str(" ")?;
+            write_list(f, button, '(', ')')?;
+        }
+        f.write_str(" ")?;
+        write_list(f, &self.target_joltage, '{', '}')
     }
 }
 
 // ------------------------------------------------------------
 // Trait implementation
 // ------------------------------------------------------------
-impl Solution for Day10 {
-    // Takes machine description lines, parses each non-empty line, and stores all machines.
-    fn set_input(&mut self, lines: &[String]) {
-        self.machines.clear();
-        for line in lines {
-            if !line.trim().is_empty() {
-                self.machines.push(parse_machine(line));
-            }
-        }
+impl Puzzle for Day10 {
+    type Input = Parsed;
+
+    const PARTS: &'static [Part<Self>] = &[
+        Part::new(
+            1,
+            "indicator-lights",
+            "Fewest button presses to configure every machine's indicator lights",
+            Self::part1,
+        ),
+        Part::new(
+            2,
+            "joltage",
+            "Fewest button presses to reach every machine's joltage levels",
+            Self::part2,
+        ),
+    ];
+    const VARIANTS: &'static [Variant<Self>] = &[Variant::new(
+        2,
+        "brute-force",
+        "Tries every press count per button; only practical for small machines",
+        Self::part2_brute_force,
+    )];
+
+    // Expects one machine per line.
+    fn input_shape() -> InputShape {
+        InputShape::Lines
+    }
+
+    // Takes machine description lines and returns the parsed machines.
+    fn parse(input: &Input) -> Result<Parsed, ParseError> {
+        let Lines(machines) = input.as_str().parse()?;
+        Ok(Parsed { machines })
     }
+}
 
+impl Day10 {
     // Solves all machines' light states in parallel and returns the summed minimum button presses.
-    fn part1(&mut self) -> String {
-        self.machines
+    fn part1(input: &Parsed) -> String {
+        input
+            .machines
             .par_iter()
-            .map(|machine| Self::fewest_light_presses(machine) as i64)
+            .map(|machine| Parsed::fewest_light_presses(machine) as i64)
             .sum::<i64>()
             .to_string()
     }
 
     // Solves all machines' joltage targets in parallel and returns the summed minimum button presses.
-    fn part2(&mut self) -> String {
-        self.machines
+    fn part2(input: &Parsed) -> String {
+        input
+            .machines
             .par_iter()
-            .map(Self::fewest_joltage_presses)
+            .map(Parsed::fewest_joltage_presses)
+            .sum::<i64>()
+            .to_string()
+    }
+
+    // Searches every machine's button presses exhaustively in parallel and returns the summed minimum: the reference
+    // for `part2`.
+    fn part2_brute_force(input: &Parsed) -> String {
+        input
+            .machines
+            .par_iter()
+            .map(Parsed::brute_joltage_presses)
             .sum::<i64>()
             .to_string()
     }
@@ -400,8 +593,8 @@ mod tests {
         "[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}",
     ];
 
-    fn example_input() -> Vec<String> {
-        EXAMPLE.iter().map(ToString::to_string).collect()
+    fn example_input() -> Input {
+        Input::from_lines(&EXAMPLE)
     }
 
     fn brute_joltage(machine: &Machine) -> i64 {
@@ -446,19 +639,48 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        let mut day = Day10::new();
-        day.set_input(&example_input());
-        assert_eq!(day.part1(), "7");
+        let input = Day10::parse(&example_input()).unwrap();
+        assert_eq!(Day10::part1(&input), "7");
+    }
+
+    #[test]
+    fn input_model_round_trips() {
+        let machines: Machines = example_input().as_str().parse().unwrap();
+        assert_eq!(machines.0[0].target_lights, vec![0, 1, 1, 0]);
+        assert_eq!(machines.0[0].buttons[1], vec![1, 3]);
+        assert_eq!(machines.0[2].target_joltage, vec![10, 11, 11, 5, 10, 5]);
+        assert_eq!(machines.to_string(), example_input().as_str());
+
+        for bad in [
+            "[.#] (0) (1)",
+            "[.x] (0) {1}",
+            "[.#] (0 {1}",
+            "[.#] {1} (0)",
+            "[.#] (0,5) (1) {1,1}",
+        ] {
+            assert!(bad.parse::<Machine>().is_err(), "{bad}");
+        }
     }
 
     #[test]
     fn joltage_solver_matches_brute_force_on_example() {
         for line in EXAMPLE {
-            let machine = parse_machine(line);
+            let machine: Machine = line.parse().unwrap();
+            assert_eq!(
+                Parsed::fewest_joltage_presses(&machine),
+                brute_joltage(&machine)
+            );
             assert_eq!(
-                Day10::fewest_joltage_presses(&machine),
+                Parsed::brute_joltage_presses(&machine),
                 brute_joltage(&machine)
             );
         }
     }
+
+    #[test]
+    fn joltage_solver_matches_brute_force_on_generated_machines() {
+        let lines = crate::inputgen::generate(10, 3, crate::inputgen::DEFAULT_SEED).unwrap();
+        let input = Day10::parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(Day10::part2(&input), Day10::part2_brute_force(&input));
+    }
 }
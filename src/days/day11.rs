@@ -1,8 +1,98 @@
-use crate::days::Solution;
+use crate::days::{Part, Puzzle};
+use crate::input::{Input, InputShape};
+use crate::parse::{Lines, ParseError};
+use serde::Serialize;
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 
+// One device and the devices its outputs feed, written "aaa: you hhh".
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Device {
+    pub name: String,
+    pub outputs: Vec<String>,
+}
+
+// The whole puzzle input: one device per line.
+pub type Devices = Lines<Device>;
+
+impl FromStr for Device {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, ParseError> {
+        let (name, outputs) = line
+            .split_once(':')
+            .filter(|(name, _)| !name.trim().is_empty())
+            .ok_or_else(|| {
+                ParseError::new(format!("devices must look like aaa: bbb ccc: {line:?}"))
+            })?;
+        Ok(Self {
+            name: name.trim().to_string(),
+            outputs: outputs.split_whitespace().map(str::to_string).collect(),
+        })
+    }
+}
+
+impl fmt::Display for Device {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.name)?;
+        for output in &self.outputs {
+            write!(f, " {output}")?;
+        }
+        Ok(())
+    }
+}
+
+// The devices with their names interned: node i is `names[i]` and feeds the nodes in `adjacency[i]`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct DeviceGraph<'a> {
+    pub names: Vec<&'a str>,
+    pub adjacency: Vec<Vec<usize>>,
+    #[serde(skip)]
+    ids: HashMap<&'a str, usize>,
+}
+
+impl<'a> DeviceGraph<'a> {
+    // Takes the parsed devices, numbers every name in order of first appearance, and returns the graph.
+    pub fn new(devices: &'a [Device]) -> Self {
+        let mut graph = Self {
+            ids: HashMap::with_capacity(devices.len() * 2),
+            ..Self::default()
+        };
+        for device in devices {
+            for name in std::iter::once(&device.name).chain(&device.outputs) {
+                if !graph.ids.contains_key(name.as_str()) {
+                    graph.ids.insert(name, graph.names.len());
+                    graph.names.push(name);
+                }
+            }
+        }
+
+        graph.adjacency.resize_with(graph.names.len(), Vec::new);
+        for device in devices {
+            let node = graph.ids[device.name.as_str()];
+            graph.adjacency[node].reserve(device.outputs.len());
+            graph.adjacency[node].extend(
+                device
+                    .outputs
+                    .iter()
+                    .map(|output| graph.ids[output.as_str()]),
+            );
+        }
+        graph
+    }
+
+    // Takes a device name and returns its node, or None if it never appears.
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+}
+
+pub struct Day11;
+
+// The wiring graph as the parts read it.
 #[derive(Default)]
-pub struct Day11 {
+pub struct Parsed {
     graph: Vec<Vec<usize>>,
     you: Option<usize>,
     svr: Option<usize>,
@@ -11,7 +101,7 @@ pub struct Day11 {
     out: Option<usize>,
 }
 
-impl Day11 {
+impl Parsed {
     // Counts paths from the requested node to out with a dense memo indexed by node ID.
     fn count_paths_from(&self, start: Option<usize>) -> u64 {
         fn dfs(node: usize, out: usize, graph: &[Vec<usize>], memo: &mut [Option<u64>]) -> u64 {
@@ -81,72 +171,56 @@ impl Day11 {
     }
 }
 
-impl Solution for Day11 {
-    // Interns device names once and stores the wiring as a compact integer-indexed graph.
-    fn set_input(&mut self, lines: &[String]) {
-        let parsed: Vec<(&str, Vec<&str>)> = lines
-            .iter()
-            .filter_map(|line| {
-                let line = line.trim();
-                if line.is_empty() {
-                    return None;
-                }
-                let (device, outputs) = line.split_once(':').unwrap_or((line, ""));
-                Some((device.trim(), outputs.split_whitespace().collect()))
-            })
-            .collect();
-
-        let mut ids = HashMap::with_capacity(parsed.len() * 2);
-        for (device, outputs) in &parsed {
-            if !ids.contains_key(device) {
-                let id = ids.len();
-                ids.insert(*device, id);
-            }
-            for output in outputs {
-                if !ids.contains_key(output) {
-                    let id = ids.len();
-                    ids.insert(*output, id);
-                }
-            }
-        }
+impl Puzzle for Day11 {
+    type Input = Parsed;
 
-        self.graph.clear();
-        self.graph.resize_with(ids.len(), Vec::new);
-        for (device, outputs) in parsed {
-            let node = ids[device];
-            self.graph[node].reserve(outputs.len());
-            self.graph[node].extend(outputs.into_iter().map(|output| ids[output]));
-        }
+    const PARTS: &'static [Part<Self>] = &[
+        Part::new(1, "paths-from-you", "Paths from you to out", Self::part1),
+        Part::new(
+            2,
+            "paths-via-dac-fft",
+            "Paths from svr to out that visit both dac and fft",
+            Self::part2,
+        ),
+    ];
+
+    // Expects one device per line.
+    fn input_shape() -> InputShape {
+        InputShape::Lines
+    }
+
+    // Interns device names once and returns the wiring as a compact integer-indexed graph.
+    fn parse(input: &Input) -> Result<Parsed, ParseError> {
+        let Lines(devices) = input.as_str().parse::<Devices>()?;
+        let graph = DeviceGraph::new(&devices);
 
-        self.you = ids.get("you").copied();
-        self.svr = ids.get("svr").copied();
-        self.dac = ids.get("dac").copied();
-        self.fft = ids.get("fft").copied();
-        self.out = ids.get("out").copied();
+        Ok(Parsed {
+            you: graph.id("you"),
+            svr: graph.id("svr"),
+            dac: graph.id("dac"),
+            fft: graph.id("fft"),
+            out: graph.id("out"),
+            graph: graph.adjacency,
+        })
     }
+}
 
-    fn part1(&mut self) -> String {
-        self.count_paths_from(self.you).to_string()
+impl Day11 {
+    fn part1(input: &Parsed) -> String {
+        input.count_paths_from(input.you).to_string()
     }
 
-    fn part2(&mut self) -> String {
-        self.count_svr_paths_via_dac_and_fft().to_string()
+    fn part2(input: &Parsed) -> String {
+        input.count_svr_paths_via_dac_and_fft().to_string()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::{Adapter, Solution};
     use pretty_assertions::assert_eq;
 
-    fn split_lines(s: &str) -> Vec<String> {
-        s.lines()
-            .map(|line| line.trim_end_matches('\r'))
-            .filter(|line| !line.trim().is_empty())
-            .map(str::to_string)
-            .collect()
-    }
-
     #[test]
     fn part1_example() {
         let input = r#"
@@ -161,9 +235,9 @@ ggg: out
 hhh: ccc fff iii
 iii: out
 "#;
-        let mut d = Day11::default();
-        d.set_input(&split_lines(input));
-        assert_eq!(d.part1(), "5");
+        let mut d = Adapter::<Day11>::new();
+        crate::days::set_input(&mut d, &Input::from(input)).unwrap();
+        assert_eq!(d.solve_part(1), "5");
     }
 
     #[test]
@@ -183,8 +257,41 @@ fff: ggg hhh
 ggg: out
 hhh: out
 "#;
-        let mut d = Day11::default();
-        d.set_input(&split_lines(input));
-        assert_eq!(d.part2(), "2");
+        let mut d = Adapter::<Day11>::new();
+        crate::days::set_input(&mut d, &Input::from(input)).unwrap();
+        assert_eq!(d.solve_part(2), "2");
+    }
+
+    #[test]
+    fn input_model_round_trips() {
+        let text = "aaa: you hhh\nyou: bbb ccc\nbbb:\n";
+        let devices: Devices = text.parse().unwrap();
+        assert_eq!(
+            devices.0[1],
+            Device {
+                name: "you".to_string(),
+                outputs: vec!["bbb".to_string(), "ccc".to_string()],
+            }
+        );
+        assert_eq!(devices.to_string(), text);
+
+        assert!("aaa: bbb\nccc ddd\n".parse::<Devices>().is_err());
+    }
+
+    #[test]
+    fn graph_interns_names_in_order_of_appearance() {
+        let devices: Devices = "aaa: you hhh\nyou: hhh out\n".parse().unwrap();
+        let graph = DeviceGraph::new(&devices.0);
+        assert_eq!(graph.names, vec!["aaa", "you", "hhh", "out"]);
+        assert_eq!(
+            graph.adjacency,
+            vec![vec![1, 2], vec![2, 3], vec![], vec![]]
+        );
+        assert_eq!((graph.id("out"), graph.id("svr")), (Some(3), None));
+
+        let json = crate::days::dump_parsed(11, "aaa: you\n").unwrap().unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["devices"][0]["outputs"][0], "you");
+        assert_eq!(json["graph"]["adjacency"], serde_json::json!([[1], []]));
     }
 }
@@ -1,31 +1,47 @@
-use crate::days::Solution;
+use crate::days::{Part, Puzzle};
+use crate::input::{self, Input, InputShape};
+use crate::parse::ParseError;
 use rayon::prelude::*;
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+// An (x, y) cell offset.
+pub type Cell = (i32, i32);
+
+// One present shape as drawn in the input: its index and the '#' cells of its width x height drawing.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Shape {
+    pub index: usize,
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<Cell>,
+}
 
-type Cell = (i32, i32);
-
-#[derive(Clone, Eq, PartialEq, Ord, PartialOrd)]
-struct Shape {
-    cells: Vec<Cell>,
+// One region under a tree, written "12x5: 1 0 1 0 2 2": its size and how many of each shape must fit.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Region {
+    pub width: usize,
+    pub height: usize,
+    pub shape_counts: Vec<usize>,
 }
 
-#[derive(Clone)]
-struct Region {
-    width: usize,
-    height: usize,
-    shape_counts: Vec<usize>,
+// The whole puzzle input: the shape drawings, each followed by a blank line, then one region per line.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TreeFarm {
+    pub shapes: Vec<Shape>,
+    pub regions: Vec<Region>,
 }
 
-#[derive(Default)]
-pub struct Day12 {
-    shapes: Vec<Shape>,
-    regions: Vec<Region>,
+// One shape orientation with its cells shifted to the origin and sorted.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd)]
+struct Orientation {
+    cells: Vec<Cell>,
 }
 
-impl Day12 {
-    pub fn new() -> Self {
-        Self::default()
-    }
+pub struct Day12;
 
+impl TreeFarm {
     // ------------------------------------------------------------
     // Geometry helpers
     // ------------------------------------------------------------
@@ -39,10 +55,10 @@ impl Day12 {
         normalized
     }
 
-    // Takes one shape, generates all rotations/flips, deduplicates them, and returns unique orientations.
-    fn orientations(shape: &Shape) -> Vec<Shape> {
+    // Takes one shape's cells, generates all rotations/flips, deduplicates them, and returns unique orientations.
+    fn orientations(cells: &[Cell]) -> Vec<Orientation> {
         let mut orientations = Vec::new();
-        let mut rotated = shape.cells.clone();
+        let mut rotated = cells.to_vec();
 
         for _ in 0..4 {
             rotated = rotated.iter().map(|(x, y)| (*y, -*x)).collect();
@@ -52,7 +68,7 @@ impl Day12 {
                 } else {
                     rotated.clone()
                 };
-                orientations.push(Shape {
+                orientations.push(Orientation {
                     cells: Self::normalize(&cells),
                 });
             }
@@ -64,13 +80,13 @@ impl Day12 {
     }
 
     // Takes an oriented shape and region size, enumerates valid placements, and returns occupied cell indices.
-    fn placements(shape: &Shape, width: usize, height: usize) -> Vec<Vec<usize>> {
+    fn placements(orientation: &Orientation, width: usize, height: usize) -> Vec<Vec<usize>> {
         let mut placements = Vec::new();
         for x in 0..width as i32 {
             for y in 0..height as i32 {
                 let mut cells = Vec::new();
                 let mut fits = true;
-                for (dx, dy) in &shape.cells {
+                for (dx, dy) in &orientation.cells {
                     let nx = x + dx;
                     let ny = y + dy;
                     if nx < 0 || ny < 0 || nx >= width as i32 || ny >= height as i32 {
@@ -100,7 +116,7 @@ impl Day12 {
         let mut placements_by_shape: Vec<Vec<Vec<usize>>> = Vec::new();
         for shape in shapes {
             let mut placements = Vec::new();
-            for orientation in Self::orientations(shape) {
+            for orientation in Self::orientations(&shape.cells) {
                 placements.extend(Self::placements(&orientation, region.width, region.height));
             }
             placements_by_shape.push(placements);
@@ -169,65 +185,147 @@ impl Day12 {
 // Parsing
 // ------------------------------------------------------------
 
-// Takes the mixed shape/region input, parses shape cells and region counts, and fills the provided vectors.
-fn parse_day12(lines: &[String], shapes: &mut Vec<Shape>, regions: &mut Vec<Region>) {
-    let mut i = 0;
+// Takes a shape header such as "4:" and returns the shape index.
+fn parse_shape_header(line: &str) -> Option<usize> {
+    let index = line.strip_suffix(':')?;
+    if index.is_empty() || !index.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    index.parse().ok()
+}
+
+// Takes a region line such as "12x5: 1 0 1 0 2 2" and returns the region.
+fn parse_region(line: &str) -> Result<Region, ParseError> {
+    let invalid = || ParseError::new(format!("regions must look like 12x5: 1 0 2: {line:?}"));
+    let (size, counts) = line.split_once(':').ok_or_else(invalid)?;
+    let (width, height) = size.trim().split_once('x').ok_or_else(invalid)?;
+    Ok(Region {
+        width: width.parse().map_err(|_| invalid())?,
+        height: height.parse().map_err(|_| invalid())?,
+        shape_counts: counts
+            .split_whitespace()
+            .map(|count| count.parse().map_err(|_| invalid()))
+            .collect::<Result<_, _>>()?,
+    })
+}
 
-    while i < lines.len() {
-        let line = lines[i].as_str();
+impl FromStr for TreeFarm {
+    type Err = ParseError;
 
-        // ----------------------------
-        // Shape block: "N:"
-        // ----------------------------
-        if line.ends_with(':') && line[..line.len() - 1].chars().all(|c| c.is_ascii_digit()) {
-            i += 1;
-            let mut cells = Vec::new();
-            let mut y = 0;
+    // Shape headers start a drawing of '#' and '.' rows that runs until a blank line; every other
+    // non-blank line must be a region.
+    fn from_str(text: &str) -> Result<Self, ParseError> {
+        let mut farm = Self::default();
+        let mut drawing: Option<Vec<&str>> = None;
 
-            while i < lines.len() {
-                let l = lines[i].as_str();
+        for (index, line) in input::lines(text).enumerate() {
+            let line = line.trim();
+            let at_line = |e: ParseError| e.at_line(index + 1);
 
-                // Stop if next shape or region starts
-                if l.ends_with(':') || l.contains('x') {
-                    break;
+            if let Some(rows) = &mut drawing {
+                if !line.is_empty() && line.bytes().all(|c| c == b'#' || c == b'.') {
+                    rows.push(line);
+                    continue;
                 }
+                let shape_index = farm.shapes.len();
+                let rows = drawing.take().unwrap();
+                farm.shapes
+                    .push(shape_from_rows(shape_index, &rows).map_err(at_line)?);
+            }
 
-                for (x, c) in l.chars().enumerate() {
-                    if c == '#' {
-                        cells.push((x as i32, y));
-                    }
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(shape_index) = parse_shape_header(line) {
+                if shape_index != farm.shapes.len() {
+                    return Err(at_line(ParseError::new(format!(
+                        "expected shape {} but found shape {shape_index}",
+                        farm.shapes.len()
+                    ))));
                 }
-                y += 1;
-                i += 1;
+                drawing = Some(Vec::new());
+            } else {
+                let region = parse_region(line).map_err(at_line)?;
+                if region.shape_counts.len() > farm.shapes.len() {
+                    return Err(at_line(ParseError::new(format!(
+                        "region lists {} shape counts but only {} shapes are drawn",
+                        region.shape_counts.len(),
+                        farm.shapes.len()
+                    ))));
+                }
+                farm.regions.push(region);
             }
+        }
+        if let Some(rows) = drawing {
+            let shape_index = farm.shapes.len();
+            farm.shapes.push(shape_from_rows(shape_index, &rows)?);
+        }
+        Ok(farm)
+    }
+}
+
+// Takes a shape index and its drawing rows, and returns the shape, or an error for an empty or ragged drawing.
+fn shape_from_rows(index: usize, rows: &[&str]) -> Result<Shape, ParseError> {
+    let width = rows.first().map_or(0, |row| row.len());
+    if rows.iter().any(|row| row.len() != width) {
+        return Err(ParseError::new(format!(
+            "shape {index}'s rows must all be the same width"
+        )));
+    }
+    let cells: Vec<Cell> = rows
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.bytes()
+                .enumerate()
+                .filter(|&(_, c)| c == b'#')
+                .map(move |(x, _)| (x as i32, y as i32))
+        })
+        .collect();
+    if cells.is_empty() {
+        return Err(ParseError::new(format!("shape {index} has no '#' cells")));
+    }
+    Ok(Shape {
+        index,
+        width,
+        height: rows.len(),
+        cells,
+    })
+}
 
-            shapes.push(Shape {
-                cells: Day12::normalize(&cells),
-            });
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}:", self.index)?;
+        let mut drawing = vec![vec![b'.'; self.width]; self.height];
+        for &(x, y) in &self.cells {
+            drawing[y as usize][x as usize] = b'#';
         }
-        // ----------------------------
-        // Region: "WxH: c c c c c c"
-        // ----------------------------
-        else if line.contains('x') && line.contains(':') {
-            let parts: Vec<_> = line.split(':').collect();
-            let dims: Vec<_> = parts[0].split('x').collect();
-
-            let width = dims[0].parse::<usize>().unwrap();
-            let height = dims[1].parse::<usize>().unwrap();
-            let shape_counts = parts[1]
-                .split_whitespace()
-                .map(|x| x.parse::<usize>().unwrap())
-                .collect();
-
-            regions.push(Region {
-                width,
-                height,
-                shape_counts,
-            });
-            i += 1;
-        } else {
-            i += 1;
+        for row in drawing {
+            writeln!(f, "{}", String::from_utf8_lossy(&row))?;
         }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}:", self.width, self.height)?;
+        for count in &self.shape_counts {
+            write!(f, " {count}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TreeFarm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for shape in &self.shapes {
+            writeln!(f, "{shape}")?;
+        }
+        for region in &self.regions {
+            writeln!(f, "{region}")?;
+        }
+        Ok(())
     }
 }
 
@@ -237,19 +335,34 @@ fn parse_day12(lines: &[String], shapes: &mut Vec<Shape>, regions: &mut Vec<Regi
 
 const SMALL_BOARD_MAX_AREA12: usize = 15 * 15;
 
-impl Solution for Day12 {
-    // Takes the full puzzle input, parses present shapes and target regions, and stores both.
-    fn set_input(&mut self, lines: &[String]) {
-        self.shapes.clear();
-        self.regions.clear();
-        parse_day12(lines, &mut self.shapes, &mut self.regions);
+impl Puzzle for Day12 {
+    type Input = TreeFarm;
+
+    const PARTS: &'static [Part<Self>] = &[Part::new(
+        1,
+        "regions-that-fit",
+        "Regions that can fit all of their presents",
+        Self::part1,
+    )];
+
+    // Expects each shape drawing in its own section, then a last section of regions.
+    fn input_shape() -> InputShape {
+        InputShape::Sections(2..=usize::MAX)
     }
 
+    // Takes the shape drawings and region lines and returns them parsed.
+    fn parse(input: &Input) -> Result<TreeFarm, ParseError> {
+        input.as_str().parse()
+    }
+}
+
+impl Day12 {
     // Checks each region for fit feasibility in parallel and returns how many regions can fit their presents.
-    fn part1(&mut self) -> String {
-        let shapes = &self.shapes;
+    fn part1(input: &TreeFarm) -> String {
+        let shapes = &input.shapes;
 
-        self.regions
+        input
+            .regions
             .par_iter()
             .filter(|region| {
                 let needed_area: usize = shapes
@@ -269,16 +382,11 @@ impl Solution for Day12 {
                     return true;
                 }
 
-                Day12::can_pack(region, shapes)
+                TreeFarm::can_pack(region, shapes)
             })
             .count()
             .to_string()
     }
-
-    // Day 12 has no implemented second part in this codebase and returns a placeholder.
-    fn part2(&mut self) -> String {
-        "N/A".to_string()
-    }
 }
 
 // ------------------------------------------------------------
@@ -288,7 +396,7 @@ impl Solution for Day12 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::days::Solution;
+    use crate::days::{Adapter, Solution};
 
     const DAY12_EXAMPLE: &str = r#"
 0:
@@ -326,22 +434,48 @@ mod tests {
 12x5: 1 0 1 0 3 2
 "#;
 
-    fn split_lines(s: &str) -> Vec<String> {
-        s.lines()
-            .map(|l| l.trim_end())
-            .filter(|l| !l.is_empty())
-            .map(|l| l.to_string())
-            .collect()
-    }
-
     #[test]
     fn part1_example() {
-        let mut d = Day12::new();
-        d.set_input(&split_lines(DAY12_EXAMPLE));
+        let mut d = Adapter::<Day12>::new();
+        crate::days::set_input(&mut d, &Input::from(DAY12_EXAMPLE)).unwrap();
 
-        let got = d.part1();
+        let got = d.solve_part(1);
         let want = "2";
 
         assert_eq!(got, want);
     }
+
+    #[test]
+    fn input_model_round_trips() {
+        let farm: TreeFarm = DAY12_EXAMPLE.parse().unwrap();
+        assert_eq!(farm.shapes.len(), 6);
+        assert_eq!((farm.shapes[4].width, farm.shapes[4].height), (3, 3));
+        assert_eq!(farm.shapes[4].cells.len(), 7);
+        assert_eq!(
+            farm.regions[1],
+            Region {
+                width: 12,
+                height: 5,
+                shape_counts: vec![1, 0, 1, 0, 2, 2],
+            }
+        );
+        assert_eq!(farm.to_string(), DAY12_EXAMPLE.trim_start());
+
+        // Blank lines between shapes are optional, so inputs with them stripped parse the same.
+        let compact = DAY12_EXAMPLE.replace("\n\n", "\n");
+        assert_eq!(compact.parse::<TreeFarm>().unwrap(), farm);
+
+        for bad in [
+            "0:\n#.\n##.\n",
+            "1:\n#\n",
+            "0:\n..\n",
+            "4x4 0 1\n",
+            "4y4: 0\n",
+        ] {
+            assert!(bad.parse::<TreeFarm>().is_err(), "{bad}");
+        }
+
+        let error = "0:\n#\n\n2x2: 1 1\n".parse::<TreeFarm>().unwrap_err();
+        assert_eq!(error.line(), Some(4));
+    }
 }
@@ -1,7 +1,249 @@
+use crate::input::{self, Input, InputShape};
+use crate::parse::ParseError;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::io::{self, BufRead};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
 pub trait Solution: Send {
-    fn set_input(&mut self, lines: &[String]);
-    fn part1(&mut self) -> String;
-    fn part2(&mut self) -> String;
+    // Returns how this day's puzzle input is laid out; `days::set_input` checks it before solving.
+    fn input_shape(&self) -> InputShape;
+    // Returns the parts this solver answers, in the order they run.
+    fn declared_parts(&self) -> Vec<PartInfo>;
+    // Takes the puzzle input and parses it for the parts, or returns why it is malformed.
+    fn set_input(&mut self, input: &Input) -> Result<(), ParseError>;
+    // Takes the number of a declared part and returns its answer.
+    fn solve_part(&mut self, number: u8) -> String;
+    // Returns the other implementations registered for the declared parts.
+    fn variants(&self) -> Vec<VariantInfo>;
+    // Takes a declared part and one of its variants (or `DEFAULT_VARIANT`) and returns that implementation's answer.
+    fn solve_variant(&mut self, part: u8, variant: &str) -> String;
+
+    // Takes a reader over the puzzle input and consumes it line by line in place of `set_input`, for inputs larger than memory.
+    //
+    // Days whose parts need the whole input at once keep this default, which reads nothing and reports Unsupported.
+    fn stream_input(&mut self, _reader: &mut dyn BufRead) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "this day needs its whole input at once and cannot stream it",
+        ))
+    }
+
+    // Returns the declared parts over the input already set, callable from several threads at once, or None
+    // for solvers whose parts need `&mut self`.
+    fn parts(&self) -> Option<&dyn Parts> {
+        None
+    }
+}
+
+// The declared parts of a solver whose input is set, taking `&self` so they can run concurrently.
+pub trait Parts: Sync {
+    fn solve_part(&self, number: u8) -> String;
+    fn solve_variant(&self, part: u8, variant: &str) -> String;
+}
+
+// One answer a day declares: the number `--part` selects it by, a short name that selects it too, and what it computes.
+//
+// Parts 1 and 2 are the puzzle's own; a day without a second part declares only part 1, and bonus parts
+// (visual answers, community variants) take the numbers after 2.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct PartInfo {
+    pub number: u8,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+impl PartInfo {
+    // Takes a part number or name as typed on the command line and returns whether it selects this part.
+    pub fn matches(&self, selector: &str) -> bool {
+        selector.trim().parse() == Ok(self.number)
+            || selector.trim().eq_ignore_ascii_case(self.name)
+    }
+}
+
+// A declared part of a `Puzzle`: its description and the function that answers it.
+pub struct Part<P: Puzzle> {
+    pub info: PartInfo,
+    pub solve: fn(&P::Input) -> String,
+}
+
+impl<P: Puzzle> Part<P> {
+    pub const fn new(
+        number: u8,
+        name: &'static str,
+        description: &'static str,
+        solve: fn(&P::Input) -> String,
+    ) -> Self {
+        Self {
+            info: PartInfo {
+                number,
+                name,
+                description,
+            },
+            solve,
+        }
+    }
+}
+
+// The name `--variant` and `crosscheck` give a part's own implementation.
+pub const DEFAULT_VARIANT: &str = "default";
+
+// Another implementation of a declared part, usually a naive reference for the optimized default;
+// `--variant` runs it instead of the default and `crosscheck` compares the two.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct VariantInfo {
+    pub part: u8,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+// A registered variant of a `Puzzle` part: its description and the function that answers it.
+pub struct Variant<P: Puzzle> {
+    pub info: VariantInfo,
+    pub solve: fn(&P::Input) -> String,
+}
+
+impl<P: Puzzle> Variant<P> {
+    pub const fn new(
+        part: u8,
+        name: &'static str,
+        description: &'static str,
+        solve: fn(&P::Input) -> String,
+    ) -> Self {
+        Self {
+            info: VariantInfo {
+                part,
+                name,
+                description,
+            },
+            solve,
+        }
+    }
+}
+
+// A day split into a pure `parse` and the parts it declares, which only read its result.
+//
+// Every day implements this; `Adapter` runs one behind the `Solution` interface.
+pub trait Puzzle: Sized + 'static {
+    // The parsed input, shared read-only by all parts.
+    type Input: Send + Sync + 'static;
+
+    // The parts this day answers, in the order they run.
+    const PARTS: &'static [Part<Self>];
+    // Other implementations of those parts; most days have none.
+    const VARIANTS: &'static [Variant<Self>] = &[];
+
+    // Returns how the puzzle input is laid out; `days::set_input` checks it before parsing.
+    fn input_shape() -> InputShape;
+    // Takes the puzzle input and returns it parsed, or the first error in it.
+    fn parse(input: &Input) -> Result<Self::Input, ParseError>;
+
+    // Takes a reader over the puzzle input and returns it parsed line by line, for inputs larger than memory.
+    //
+    // Days whose parts need the whole input at once keep this default, which reads nothing and reports Unsupported.
+    fn stream(_reader: &mut dyn BufRead) -> io::Result<Self::Input> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "this day needs its whole input at once and cannot stream it",
+        ))
+    }
+}
+
+// A `Puzzle` behind the `Solution` interface: `set_input` parses and keeps the result for the parts.
+pub struct Adapter<P: Puzzle> {
+    input: Option<P::Input>,
+    puzzle: PhantomData<fn() -> P>,
+}
+
+impl<P: Puzzle> Adapter<P> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Returns the parsed input, panicking if no input was set yet.
+    fn input(&self) -> &P::Input {
+        self.input
+            .as_ref()
+            .expect("set_input or stream_input must run before the parts")
+    }
+
+    // Takes a part number and variant name and returns that implementation run on the parsed input, panicking
+    // if the day does not declare it.
+    fn run(&self, number: u8, variant: &str) -> String {
+        let solve = if variant == DEFAULT_VARIANT {
+            P::PARTS
+                .iter()
+                .find(|part| part.info.number == number)
+                .unwrap_or_else(|| panic!("this day declares no part {number}"))
+                .solve
+        } else {
+            P::VARIANTS
+                .iter()
+                .find(|v| v.info.part == number && v.info.name == variant)
+                .unwrap_or_else(|| panic!("part {number} has no variant {variant:?}"))
+                .solve
+        };
+        solve(self.input())
+    }
+}
+
+impl<P: Puzzle> Default for Adapter<P> {
+    fn default() -> Self {
+        Self {
+            input: None,
+            puzzle: PhantomData,
+        }
+    }
+}
+
+impl<P: Puzzle> Solution for Adapter<P> {
+    fn input_shape(&self) -> InputShape {
+        P::input_shape()
+    }
+
+    fn declared_parts(&self) -> Vec<PartInfo> {
+        P::PARTS.iter().map(|part| part.info).collect()
+    }
+
+    fn set_input(&mut self, input: &Input) -> Result<(), ParseError> {
+        self.input = None;
+        self.input = Some(P::parse(input)?);
+        Ok(())
+    }
+
+    fn solve_part(&mut self, number: u8) -> String {
+        self.run(number, DEFAULT_VARIANT)
+    }
+
+    fn variants(&self) -> Vec<VariantInfo> {
+        P::VARIANTS.iter().map(|variant| variant.info).collect()
+    }
+
+    fn solve_variant(&mut self, part: u8, variant: &str) -> String {
+        self.run(part, variant)
+    }
+
+    fn stream_input(&mut self, reader: &mut dyn BufRead) -> io::Result<()> {
+        self.input = None;
+        self.input = Some(P::stream(reader)?);
+        Ok(())
+    }
+
+    fn parts(&self) -> Option<&dyn Parts> {
+        self.input.as_ref().map(|_| self as &dyn Parts)
+    }
+}
+
+impl<P: Puzzle> Parts for Adapter<P> {
+    fn solve_part(&self, number: u8) -> String {
+        self.run(number, DEFAULT_VARIANT)
+    }
+
+    fn solve_variant(&self, part: u8, variant: &str) -> String {
+        self.run(part, variant)
+    }
 }
 
 pub mod day01;
@@ -20,23 +262,190 @@ pub mod day12;
 // Takes a day number, constructs its solver if implemented, and returns it behind the shared Solution trait.
 pub fn make_solver(day: u32) -> Option<Box<dyn Solution>> {
     match day {
-        1 => Some(Box::new(day01::Day01::new())),
-        2 => Some(Box::new(day02::Day02::new())),
-        3 => Some(Box::new(day03::Day03::new())),
-        4 => Some(Box::new(day04::Day04::new())),
-        5 => Some(Box::new(day05::Day05::new())),
-        6 => Some(Box::new(day06::Day06::new())),
-        7 => Some(Box::new(day07::Day07::new())),
-        8 => Some(Box::new(day08::Day08::new())),
-        9 => Some(Box::new(day09::Day09::new())),
-        10 => Some(Box::new(day10::Day10::new())),
-        11 => Some(Box::new(day11::Day11::default())),
-        12 => Some(Box::new(day12::Day12::new())),
+        1 => Some(Box::new(Adapter::<day01::Day01>::new())),
+        2 => Some(Box::new(Adapter::<day02::Day02>::new())),
+        3 => Some(Box::new(Adapter::<day03::Day03>::new())),
+        4 => Some(Box::new(Adapter::<day04::Day04>::new())),
+        5 => Some(Box::new(Adapter::<day05::Day05>::new())),
+        6 => Some(Box::new(Adapter::<day06::Day06>::new())),
+        7 => Some(Box::new(Adapter::<day07::Day07>::new())),
+        8 => Some(Box::new(Adapter::<day08::Day08>::new())),
+        9 => Some(Box::new(Adapter::<day09::Day09>::new())),
+        10 => Some(Box::new(Adapter::<day10::Day10>::new())),
+        11 => Some(Box::new(Adapter::<day11::Day11>::new())),
+        12 => Some(Box::new(Adapter::<day12::Day12>::new())),
         _ => None,
     }
 }
 
+// Takes a solver and a loaded input, normalizes the input, checks it against the solver's declared
+// shape, and passes it to `set_input`; returns the error for input of the wrong shape or that fails to parse instead.
+//
+// Inputs that are already normal are passed on without copying.
+pub fn set_input(solver: &mut dyn Solution, input: &Input) -> Result<(), ParseError> {
+    let owned;
+    let input = match input::normalize(input.as_str()) {
+        Cow::Borrowed(_) => input,
+        Cow::Owned(text) => {
+            owned = Input::from(text);
+            &owned
+        }
+    };
+    solver.input_shape().check(input.as_str())?;
+    solver.set_input(input)
+}
+
+// Takes a day and its puzzle input, normalizes and parses the input into that day's model, and returns the model
+// written back out in the puzzle format, or None for days without a solver.
+pub fn reformat_input(day: u32, text: &str) -> Option<Result<String, ParseError>> {
+    // Takes puzzle text and returns it parsed as T and displayed again.
+    fn reformat<T: FromStr<Err = ParseError> + Display>(text: &str) -> Result<String, ParseError> {
+        text.parse::<T>().map(|model| model.to_string())
+    }
+
+    let text = &input::normalize(text);
+    Some(match day {
+        1 => reformat::<day01::Rotations>(text),
+        2 => reformat::<day02::IdRanges>(text),
+        3 => reformat::<day03::BatteryBanks>(text),
+        4 => reformat::<day04::PaperRolls>(text),
+        5 => reformat::<day05::Inventory>(text),
+        6 => reformat::<day06::Worksheet>(text),
+        7 => reformat::<day07::Manifold>(text),
+        8 => reformat::<day08::Junctions>(text),
+        9 => reformat::<day09::RedTiles>(text),
+        10 => reformat::<day10::Machines>(text),
+        11 => reformat::<day11::Devices>(text),
+        12 => reformat::<day12::TreeFarm>(text),
+        _ => return None,
+    })
+}
+
+// Takes a day and its puzzle input, normalizes and parses the input into that day's model, and returns the model
+// as pretty-printed JSON without solving, or None for days without a solver.
+//
+// Day 11 also includes the graph its solver builds, with each device name interned as a node number.
+pub fn dump_parsed(day: u32, text: &str) -> Option<Result<String, ParseError>> {
+    // Takes puzzle text and returns it parsed as T and written as JSON.
+    fn dump<T: FromStr<Err = ParseError> + Serialize>(text: &str) -> Result<String, ParseError> {
+        text.parse::<T>().map(|model| to_json(&model))
+    }
+
+    // Takes a model and returns it as JSON; models have no maps with non-string keys, so this cannot fail.
+    fn to_json(model: &impl Serialize) -> String {
+        serde_json::to_string_pretty(model).expect("input models serialize to JSON")
+    }
+
+    #[derive(Serialize)]
+    struct DevicesWithGraph<'a> {
+        devices: &'a day11::Devices,
+        graph: day11::DeviceGraph<'a>,
+    }
+
+    let text = &input::normalize(text);
+    Some(match day {
+        1 => dump::<day01::Rotations>(text),
+        2 => dump::<day02::IdRanges>(text),
+        3 => dump::<day03::BatteryBanks>(text),
+        4 => dump::<day04::PaperRolls>(text),
+        5 => dump::<day05::Inventory>(text),
+        6 => dump::<day06::Worksheet>(text),
+        7 => dump::<day07::Manifold>(text),
+        8 => dump::<day08::Junctions>(text),
+        9 => dump::<day09::RedTiles>(text),
+        10 => dump::<day10::Machines>(text),
+        11 => text.parse::<day11::Devices>().map(|devices| {
+            to_json(&DevicesWithGraph {
+                devices: &devices,
+                graph: day11::DeviceGraph::new(&devices.0),
+            })
+        }),
+        12 => dump::<day12::TreeFarm>(text),
+        _ => return None,
+    })
+}
+
+// Returns the days whose solvers implement `stream_input`.
+pub fn streaming_days() -> Vec<u32> {
+    vec![1, 3, 5]
+}
+
 // Returns the day numbers currently implemented by this crate, used by the benchmark harness.
 pub fn implemented_days() -> Vec<u32> {
     vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A puzzle with one answer, a bonus part numbered after the puzzle's own, and a (wrong) variant of part 1.
+    struct Words;
+
+    struct Text(String);
+
+    impl Words {
+        fn count(input: &Text) -> String {
+            input.0.split_whitespace().count().to_string()
+        }
+
+        fn count_lines(input: &Text) -> String {
+            input.0.lines().count().to_string()
+        }
+
+        fn shout(input: &Text) -> String {
+            input
+                .0
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_uppercase()
+        }
+    }
+
+    impl Puzzle for Words {
+        type Input = Text;
+
+        const PARTS: &'static [Part<Self>] = &[
+            Part::new(1, "count", "Number of words", Self::count),
+            Part::new(3, "shout", "The words in capitals", Self::shout),
+        ];
+        const VARIANTS: &'static [Variant<Self>] = &[Variant::new(
+            1,
+            "lines",
+            "Number of lines, which is wrong for lines with several words",
+            Self::count_lines,
+        )];
+
+        fn input_shape() -> InputShape {
+            InputShape::Lines
+        }
+
+        fn parse(input: &Input) -> Result<Text, ParseError> {
+            Ok(Text(input.as_str().to_string()))
+        }
+    }
+
+    #[test]
+    fn runs_whichever_parts_a_puzzle_declares() {
+        let mut solver = Adapter::<Words>::new();
+        set_input(&mut solver, &Input::from("a\nbc\n")).unwrap();
+
+        let parts = solver.declared_parts();
+        assert_eq!(
+            parts.iter().map(|part| part.number).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert!(parts[1].matches("3") && parts[1].matches("Shout"));
+        assert!(!parts[0].matches("2"));
+
+        assert_eq!(solver.solve_part(1), "2");
+        assert_eq!(solver.parts().unwrap().solve_part(3), "A BC");
+
+        assert_eq!(solver.variants()[0].name, "lines");
+        assert_eq!(solver.solve_variant(1, DEFAULT_VARIANT), "2");
+        set_input(&mut solver, &Input::from("a b\nc\n")).unwrap();
+        assert_eq!(solver.solve_variant(1, "lines"), "2");
+        assert_eq!(solver.solve_part(1), "3");
+    }
+}
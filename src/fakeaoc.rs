@@ -0,0 +1,887 @@
+use crate::answers::{AnswerStore, DayAnswers};
+use crate::aocnet::YEAR;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+
+// A local stand-in for adventofcode.com, for end-to-end tests that must not touch the network.
+//
+// It serves the endpoints the client uses (event page, puzzle pages, inputs, answer submission,
+// private leaderboard JSON, personal stats and the login redirect) from in-memory fixtures, and
+// can be told to fail specific paths with 404/429/5xx responses or a login redirect. Point the
+// client at it with `ClientOptions::base_url` or AOC_BASE_URL.
+
+// What the fake site knows: one account, its inputs, pages, answers and leaderboards.
+#[derive(Clone, Debug)]
+pub struct Fixtures {
+    pub year: u32,
+    // The only session cookie accepted as logged in.
+    pub session: String,
+    // Display name shown in the page header when logged in.
+    pub user: String,
+    pub inputs: BTreeMap<u32, String>,
+    // Puzzle page HTML; a minimal page is generated for days that have an input but no page.
+    pub pages: BTreeMap<u32, String>,
+    // Correct answers, used to judge submissions and shown on the page once solved.
+    pub answers: BTreeMap<u32, DayAnswers>,
+    // Day -> number of parts already solved by the account.
+    pub solved: BTreeMap<u32, u8>,
+    // Leaderboard ID -> JSON view.
+    pub leaderboards: BTreeMap<String, String>,
+    // Days that answer with the "before it unlocks" 404.
+    pub locked: BTreeSet<u32>,
+    // Content of the personal stats page; a page without any stars is served when unset.
+    pub stats: Option<String>,
+}
+
+impl Default for Fixtures {
+    fn default() -> Self {
+        Self {
+            year: YEAR,
+            session: "fake-session".to_string(),
+            user: "Fake User".to_string(),
+            inputs: BTreeMap::new(),
+            pages: BTreeMap::new(),
+            answers: BTreeMap::new(),
+            solved: BTreeMap::new(),
+            leaderboards: BTreeMap::new(),
+            locked: BTreeSet::new(),
+            stats: None,
+        }
+    }
+}
+
+impl Fixtures {
+    // Takes a directory laid out like `input/` and returns fixtures read from it.
+    //
+    // dayNN.txt files become inputs, dayNN.html files puzzle pages, leaderboard-<id>.json files
+    // leaderboards, stats.html the personal stats page, and answers.yaml holds the correct answers;
+    // every stored answer counts as solved.
+    pub fn from_dir(dir: &Path) -> io::Result<Self> {
+        let mut fixtures = Self::default();
+
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let name = path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            if let Some(day) = fixture_day(&name, ".txt") {
+                fixtures.inputs.insert(day, fs::read_to_string(&path)?);
+            } else if let Some(day) = fixture_day(&name, ".html") {
+                fixtures.pages.insert(day, fs::read_to_string(&path)?);
+            } else if name == "stats.html" {
+                fixtures.stats = Some(fs::read_to_string(&path)?);
+            } else if let Some(id) = name
+                .strip_prefix("leaderboard-")
+                .and_then(|rest| rest.strip_suffix(".json"))
+            {
+                fixtures
+                    .leaderboards
+                    .insert(id.to_string(), fs::read_to_string(&path)?);
+            }
+        }
+
+        for (day, answers) in AnswerStore::load(&dir.join("answers.yaml"))?.iter() {
+            let parts = (1..=2)
+                .take_while(|&part| answers.part(part).is_some())
+                .count();
+            fixtures.solved.insert(day, parts as u8);
+            fixtures.answers.insert(day, answers.clone());
+        }
+
+        Ok(fixtures)
+    }
+}
+
+// Takes a fixture file name and extension and returns its day for names like day07.txt.
+fn fixture_day(name: &str, extension: &str) -> Option<u32> {
+    name.strip_prefix("day")?
+        .strip_suffix(extension)?
+        .parse()
+        .ok()
+}
+
+// A scripted failure: the next `times` requests whose path starts with `path` get this response.
+#[derive(Clone, Debug)]
+pub struct Failure {
+    pub path: String,
+    pub status: u16,
+    // Response body; defaults to a short text matching the status.
+    pub body: Option<String>,
+    // Retry-After seconds sent with the response.
+    pub retry_after: Option<u64>,
+    pub times: usize,
+}
+
+impl Failure {
+    // Takes a path prefix and status and returns a failure that fires once with the default body.
+    pub fn new(path: &str, status: u16) -> Self {
+        Self {
+            path: path.to_string(),
+            status,
+            body: None,
+            retry_after: None,
+            times: 1,
+        }
+    }
+
+    // Takes a "PATH=STATUS[:TIMES]" spec, as given to the fake_aoc binary, and returns the failure.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (path, rest) = spec
+            .split_once('=')
+            .ok_or_else(|| format!("expected PATH=STATUS[:TIMES], got {spec:?}"))?;
+        let (status, times) = rest.split_once(':').unwrap_or((rest, "1"));
+        let status = status
+            .parse()
+            .map_err(|_| format!("invalid status {status:?} in {spec:?}"))?;
+        let times = times
+            .parse()
+            .map_err(|_| format!("invalid count {times:?} in {spec:?}"))?;
+        Ok(Self {
+            times,
+            ..Self::new(path, status)
+        })
+    }
+}
+
+// One request as the fake site received it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub session: Option<String>,
+    pub user_agent: Option<String>,
+    pub body: String,
+}
+
+// A response about to be written back.
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: String,
+}
+
+impl Response {
+    fn new(status: u16, content_type: &'static str, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    fn text(status: u16, body: impl Into<String>) -> Self {
+        Self::new(status, "text/plain", body)
+    }
+
+    fn html(body: impl Into<String>) -> Self {
+        Self::new(200, "text/html", body)
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    fixtures: Fixtures,
+    failures: Vec<Failure>,
+    requests: Vec<Request>,
+}
+
+// A running fake site; shuts down when dropped.
+pub struct FakeAoc {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl FakeAoc {
+    // Takes fixtures and returns a fake site listening on a free local port.
+    pub fn start(fixtures: Fixtures) -> io::Result<Self> {
+        Self::bind("127.0.0.1:0", fixtures)
+    }
+
+    // Takes a listen address and fixtures and returns a fake site serving them on that address.
+    pub fn bind(addr: impl ToSocketAddrs, fixtures: Fixtures) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State {
+            fixtures,
+            failures: Vec::new(),
+            requests: Vec::new(),
+        }));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let state = Arc::clone(&state);
+            let stop = Arc::clone(&stop);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        if let Err(e) = serve(stream, &state) {
+                            eprintln!("fake AoC: {e}");
+                        }
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            addr,
+            state,
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    // Returns the site root to use as the client's base URL.
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    // Takes a scripted failure and queues it ahead of the normal responses.
+    pub fn fail(&self, failure: Failure) {
+        lock(&self.state).failures.push(failure);
+    }
+
+    // Returns every request received so far, in order.
+    pub fn requests(&self) -> Vec<Request> {
+        lock(&self.state).requests.clone()
+    }
+
+    // Takes a day and returns how many of its parts the account has solved, including submissions.
+    pub fn solved(&self, day: u32) -> u8 {
+        lock(&self.state)
+            .fixtures
+            .solved
+            .get(&day)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    // Blocks until the server thread exits, which for a site nobody drops means forever.
+    pub fn wait(mut self) {
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for FakeAoc {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            self.stop.store(true, Ordering::SeqCst);
+            // Wake the accept loop so it sees the stop flag.
+            let _ = TcpStream::connect(self.addr);
+            let _ = handle.join();
+        }
+    }
+}
+
+// Takes the shared state and returns it locked, recovering from a panicked test thread.
+fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    state
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Takes one connection, reads a single request, answers it and closes the connection.
+fn serve(stream: TcpStream, state: &Mutex<State>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line)? == 0 {
+        return Ok(());
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default();
+    let path = target.split('?').next().unwrap_or_default().to_string();
+
+    let mut headers = BTreeMap::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let length: usize = headers
+        .get("content-length")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    let session = headers.get("cookie").and_then(|cookie| {
+        cookie
+            .split(';')
+            .find_map(|pair| pair.trim().strip_prefix("session="))
+            .map(str::to_string)
+    });
+    let request = Request {
+        method,
+        path,
+        session,
+        user_agent: headers.get("user-agent").cloned(),
+        body: String::from_utf8_lossy(&body).to_string(),
+    };
+
+    let response = respond(&mut lock(state), request);
+    write_response(stream, &response)
+}
+
+// Takes a stream and response and writes the response with Connection: close.
+fn write_response(mut stream: TcpStream, response: &Response) -> io::Result<()> {
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        reason(response.status),
+        response.content_type,
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(response.body.as_bytes())?;
+    stream.flush()
+}
+
+// Takes a status code and returns its reason phrase.
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        302 => "Found",
+        400 => "Bad Request",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        _ => "Status",
+    }
+}
+
+// Takes the site state and a request, records it, and returns the response the real site would give.
+fn respond(state: &mut State, request: Request) -> Response {
+    state.requests.push(request.clone());
+    let year = state.fixtures.year;
+
+    if let Some(index) = state
+        .failures
+        .iter()
+        .position(|failure| request.path.starts_with(&failure.path))
+    {
+        let failure = state.failures[index].clone();
+        if failure.times <= 1 {
+            state.failures.remove(index);
+        } else {
+            state.failures[index].times -= 1;
+        }
+        return scripted(&failure, year);
+    }
+
+    let logged_in = request.session.as_deref() == Some(state.fixtures.session.as_str());
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    if segments.first() != Some(&year.to_string().as_str()) {
+        return Response::text(404, "404 Not Found");
+    }
+
+    match (request.method.as_str(), &segments[1..]) {
+        ("GET", []) => Response::html(page(&state.fixtures, logged_in, "")),
+        ("GET", ["auth", "login"]) => {
+            Response::html(page(&state.fixtures, false, "<p>Log in with GitHub</p>"))
+        }
+        ("GET", ["day", day]) => match day.parse() {
+            Ok(day) if !state.fixtures.locked.contains(&day) => {
+                let article = puzzle_article(&state.fixtures, day, logged_in);
+                Response::html(page(&state.fixtures, logged_in, &article))
+            }
+            _ => Response::text(404, "404 Not Found"),
+        },
+        ("GET", ["day", day, "input"]) => {
+            let day: u32 = day.parse().unwrap_or(0);
+            if state.fixtures.locked.contains(&day) {
+                Response::text(
+                    404,
+                    "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n",
+                )
+            } else if !logged_in {
+                Response::text(
+                    400,
+                    "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+                )
+            } else {
+                match state.fixtures.inputs.get(&day) {
+                    Some(input) => Response::text(200, input.clone()),
+                    None => Response::text(404, "404 Not Found"),
+                }
+            }
+        }
+        ("POST", ["day", day, "answer"]) => {
+            if !logged_in {
+                return login_redirect(year);
+            }
+            let day: u32 = day.parse().unwrap_or(0);
+            let verdict = submit(&mut state.fixtures, day, &request.body);
+            Response::html(page(
+                &state.fixtures,
+                true,
+                &format!("<main><article><p>{verdict}</p></article></main>"),
+            ))
+        }
+        ("GET", ["leaderboard", "self"]) => {
+            if !logged_in {
+                return login_redirect(year);
+            }
+            let content = state.fixtures.stats.clone().unwrap_or_else(|| {
+                "<main><article><p>You haven't collected any stars yet.</p></article></main>"
+                    .to_string()
+            });
+            Response::html(page(&state.fixtures, true, &content))
+        }
+        ("GET", ["leaderboard", "private", "view", file]) => {
+            if !logged_in {
+                return login_redirect(year);
+            }
+            let id = file.strip_suffix(".json").unwrap_or(file);
+            match state.fixtures.leaderboards.get(id) {
+                Some(json) => Response::new(200, "application/json", json.clone()),
+                None => Response::text(404, "404 Not Found"),
+            }
+        }
+        _ => Response::text(404, "404 Not Found"),
+    }
+}
+
+// Takes a scripted failure and the event year and returns its response.
+fn scripted(failure: &Failure, year: u32) -> Response {
+    if (300..400).contains(&failure.status) {
+        return login_redirect(year);
+    }
+    let body = failure
+        .body
+        .clone()
+        .unwrap_or_else(|| match failure.status {
+            404 => "404 Not Found".to_string(),
+            429 => "Too Many Requests".to_string(),
+            status => format!("{status} {}", reason(status)),
+        });
+    let mut response = Response::text(failure.status, body);
+    if let Some(secs) = failure.retry_after {
+        response.headers.push(("Retry-After", secs.to_string()));
+    }
+    response
+}
+
+// Takes the event year and returns the redirect AoC sends logged-out visitors to.
+fn login_redirect(year: u32) -> Response {
+    let mut response = Response::text(302, "");
+    response
+        .headers
+        .push(("Location", format!("/{year}/auth/login")));
+    response
+}
+
+// Takes the fixtures, login state and main content, and returns a page with the site header.
+fn page(fixtures: &Fixtures, logged_in: bool, content: &str) -> String {
+    let user = if logged_in {
+        let stars: u32 = fixtures
+            .solved
+            .values()
+            .map(|&parts| u32::from(parts))
+            .sum();
+        format!(
+            r#"<div class="user">{} <span class="star-count">{stars}*</span></div>"#,
+            fixtures.user
+        )
+    } else {
+        format!(r#"<a href="/{}/auth/login">[Log In]</a>"#, fixtures.year)
+    };
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en-us\">\n<head><title>Advent of Code {}</title></head>\n<body>\n<header>{user}</header>\n{content}\n</body>\n</html>\n",
+        fixtures.year
+    )
+}
+
+// Takes the fixtures, a day and login state and returns the puzzle description plus any earned answers.
+fn puzzle_article(fixtures: &Fixtures, day: u32, logged_in: bool) -> String {
+    let mut article = fixtures.pages.get(&day).cloned().unwrap_or_else(|| {
+        format!(r#"<main><article class="day-desc"><h2>--- Day {day}: Fixture ---</h2></article></main>"#)
+    });
+    if !logged_in {
+        return article;
+    }
+    let solved = fixtures.solved.get(&day).copied().unwrap_or(0);
+    let answers = fixtures.answers.get(&day);
+    for part in 1..=solved {
+        if let Some(answer) = answers.and_then(|answers| answers.part(part)) {
+            article.push_str(&format!(
+                "\n<p>Your puzzle answer was <code>{answer}</code>.</p>"
+            ));
+        }
+    }
+    article
+}
+
+// Takes the fixtures, a day and the form body, judges the answer, and returns AoC's verdict text.
+fn submit(fixtures: &mut Fixtures, day: u32, form: &str) -> String {
+    let field = |name: &str| {
+        form.split('&')
+            .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+            .map(form_decode)
+            .unwrap_or_default()
+    };
+    let level: u8 = field("level").parse().unwrap_or(0);
+    let answer = field("answer");
+
+    let solved = fixtures.solved.get(&day).copied().unwrap_or(0);
+    if level != solved + 1 || fixtures.locked.contains(&day) {
+        return "You don't seem to be solving the right level.  Did you already complete it?"
+            .to_string();
+    }
+
+    let expected = fixtures
+        .answers
+        .get(&day)
+        .and_then(|answers| answers.part(level));
+    if expected == Some(answer.as_str()) {
+        fixtures.solved.insert(day, level);
+        return "That's the right answer!  You are one gold star closer to decorating the North Pole."
+            .to_string();
+    }
+
+    let hint = match (
+        expected.and_then(|e| e.parse::<i64>().ok()),
+        answer.parse::<i64>().ok(),
+    ) {
+        (Some(expected), Some(given)) if given > expected => " your answer is too high.",
+        (Some(expected), Some(given)) if given < expected => " your answer is too low.",
+        _ => "",
+    };
+    format!("That's not the right answer;{hint} Please wait one minute before trying again.")
+}
+
+// Takes a form-encoded value and returns it decoded.
+fn form_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        out.push(byte);
+                        i += 2;
+                    }
+                    Err(_) => out.push(b'%'),
+                }
+            }
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aocnet::{Client, ClientOptions, Error};
+    use crate::cache::{self, FillStatus};
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn fixtures() -> Fixtures {
+        let mut fixtures = Fixtures::default();
+        fixtures.inputs.insert(1, "L68\nL30\nR48\n".to_string());
+        fixtures.inputs.insert(2, "11-22,95-115\n".to_string());
+        fixtures.answers.insert(
+            1,
+            DayAnswers {
+                part1: Some("3".to_string()),
+                part2: Some("6".to_string()),
+            },
+        );
+        fixtures.solved.insert(1, 1);
+        fixtures.locked.insert(3);
+        fixtures
+    }
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc2025-fake-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    // AoC's verdict on a submitted answer.
+    #[derive(Debug, PartialEq, Eq)]
+    enum Submission {
+        Correct,
+        // Wrong; carries AoC's "too high"/"too low" hint when it gave one.
+        Incorrect { hint: Option<String> },
+        // Submitted too soon after a previous wrong answer.
+        TooSoon { wait: Option<String> },
+        // The part is already solved or not reachable yet.
+        WrongLevel,
+    }
+
+    // Takes the answer response page and returns the verdict, or None if it is not one (e.g. a login page).
+    fn parse_submission(html: &str) -> Option<Submission> {
+        if html.contains("That's the right answer") {
+            return Some(Submission::Correct);
+        }
+        if html.contains("That's not the right answer") {
+            let hint = ["too high", "too low"]
+                .into_iter()
+                .find(|hint| html.contains(&format!("your answer is {hint}")))
+                .map(str::to_string);
+            return Some(Submission::Incorrect { hint });
+        }
+        if html.contains("You gave an answer too recently") {
+            let wait = html.find("You have ").and_then(|pos| {
+                let rest = &html[pos + "You have ".len()..];
+                Some(rest[..rest.find(" left to wait")?].to_string())
+            });
+            return Some(Submission::TooSoon { wait });
+        }
+        if html.contains("You don't seem to be solving the right level") {
+            return Some(Submission::WrongLevel);
+        }
+        None
+    }
+
+    // Takes a form value and returns it percent-encoded for an application/x-www-form-urlencoded body.
+    fn form_encode(value: &str) -> String {
+        value
+            .bytes()
+            .map(|byte| match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                    (byte as char).to_string()
+                }
+                _ => format!("%{byte:02X}"),
+            })
+            .collect()
+    }
+
+    // Takes the site, a session, a day, part and answer, posts the answer form, and returns the verdict.
+    fn submit_answer(
+        site: &FakeAoc,
+        session: &str,
+        day: u32,
+        part: u8,
+        answer: &str,
+    ) -> Option<Submission> {
+        let html = reqwest::blocking::Client::new()
+            .post(format!("{}/{YEAR}/day/{day}/answer", site.base_url()))
+            .header(reqwest::header::COOKIE, format!("session={session}"))
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .body(format!("level={part}&answer={}", form_encode(answer)))
+            .send()
+            .unwrap()
+            .text()
+            .unwrap();
+        parse_submission(&html)
+    }
+
+    fn client(site: &FakeAoc, session: &str, dir: &Path) -> Client {
+        let options = ClientOptions {
+            contact: Some("tests".to_string()),
+            min_interval: Duration::ZERO,
+            request_log: dir.join("requests.log"),
+            base_url: site.base_url(),
+            retry_base: Duration::ZERO,
+            ..ClientOptions::default()
+        };
+        Client::new(session, options)
+    }
+
+    #[test]
+    fn fill_fetches_missing_days_and_resumes() {
+        let site = FakeAoc::start(fixtures()).unwrap();
+        let dir = scratch("fill");
+        let client = client(&site, "fake-session", &dir);
+
+        let report = cache::fill(&client, "fake", &dir, [1, 2], |_, _| {});
+        assert!(matches!(report[0].1, FillStatus::Fetched(12)));
+        assert_eq!(
+            fs::read_to_string(dir.join("day02.txt")).unwrap(),
+            "11-22,95-115\n"
+        );
+        assert_eq!(
+            cache::read_provenance(&dir.join("day01.txt"))
+                .unwrap()
+                .account,
+            "fake"
+        );
+
+        let again = cache::fill(&client, "fake", &dir, [1, 2], |_, _| {});
+        assert!(again
+            .iter()
+            .all(|(_, status)| matches!(status, FillStatus::Cached)));
+        assert_eq!(site.requests().len(), 2);
+
+        let request = &site.requests()[0];
+        assert_eq!(request.path, "/2025/day/1/input");
+        assert_eq!(request.session.as_deref(), Some("fake-session"));
+        assert_eq!(
+            request.user_agent.as_deref(),
+            Some("tests (aoc2025 Rust client)")
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn expired_session_stops_the_fill() {
+        let site = FakeAoc::start(fixtures()).unwrap();
+        let dir = scratch("expired");
+        let client = client(&site, "stale", &dir);
+
+        let report = cache::fill(&client, "fake", &dir, [1, 2], |_, _| {});
+        assert!(matches!(report[0].1, FillStatus::Failed(_)));
+        assert!(matches!(report[1].1, FillStatus::Skipped));
+        assert!(!dir.join("day01.txt").exists());
+        assert!(matches!(client.whoami(), Err(Error::Unauthorized)));
+        assert!(matches!(
+            client.fetch_leaderboard("42"),
+            Err(Error::Unauthorized)
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn transient_failures_are_retried() {
+        let site = FakeAoc::start(fixtures()).unwrap();
+        let dir = scratch("retry");
+        let client = client(&site, "fake-session", &dir);
+
+        site.fail(Failure {
+            times: 2,
+            ..Failure::new("/2025/day/1/input", 503)
+        });
+        assert_eq!(client.fetch_input(1).unwrap(), vec!["L68", "L30", "R48"]);
+        assert_eq!(site.requests().len(), 3);
+
+        site.fail(Failure {
+            retry_after: Some(0),
+            times: 5,
+            ..Failure::new("/2025/day/2", 429)
+        });
+        assert!(matches!(
+            client.fetch_input(2),
+            Err(Error::RateLimited {
+                retry_after: Some(_)
+            })
+        ));
+        assert!(matches!(
+            client.fetch_input(3),
+            Err(Error::NotYetUnlocked { .. })
+        ));
+
+        let log = fs::read_to_string(dir.join("requests.log")).unwrap();
+        assert_eq!(
+            log.lines().filter(|line| line.ends_with("\t503")).count(),
+            2
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn submissions_are_judged_and_show_up_on_the_page() {
+        let site = FakeAoc::start(fixtures()).unwrap();
+        let dir = scratch("submit");
+        let client = client(&site, "fake-session", &dir);
+
+        let page = client.fetch_puzzle_page(1).unwrap();
+        assert_eq!(crate::aocnet::parse_confirmed_answers(&page), vec!["3"]);
+        assert_eq!(client.whoami().unwrap().stars, Some(1));
+
+        assert_eq!(
+            submit_answer(&site, "fake-session", 1, 2, "7"),
+            Some(Submission::Incorrect {
+                hint: Some("too high".to_string())
+            })
+        );
+        assert_eq!(
+            submit_answer(&site, "fake-session", 1, 2, "6"),
+            Some(Submission::Correct)
+        );
+        assert_eq!(
+            submit_answer(&site, "fake-session", 1, 2, "6"),
+            Some(Submission::WrongLevel)
+        );
+        assert_eq!(site.solved(1), 2);
+
+        let page = client.fetch_puzzle_page(1).unwrap();
+        assert_eq!(
+            crate::aocnet::parse_confirmed_answers(&page),
+            vec!["3", "6"]
+        );
+
+        assert_eq!(submit_answer(&site, "stale", 2, 1, "1"), None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn fixtures_load_from_an_input_style_directory() {
+        let dir = scratch("fixtures");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day04.txt"), "@.@\n").unwrap();
+        fs::write(dir.join("leaderboard-7.json"), "{}").unwrap();
+        fs::write(dir.join("answers.yaml"), "4:\n  part1: '13'\n").unwrap();
+        fs::write(dir.join("day04.meta.yaml"), "sha256: x\n").unwrap();
+
+        let fixtures = Fixtures::from_dir(&dir).unwrap();
+        assert_eq!(fixtures.inputs.keys().collect::<Vec<_>>(), vec![&4]);
+        assert_eq!(fixtures.leaderboards["7"], "{}");
+        assert_eq!(fixtures.solved[&4], 1);
+
+        let failure = Failure::parse("/2025/day/4/input=500:3").unwrap();
+        assert_eq!((failure.status, failure.times), (500, 3));
+        assert!(Failure::parse("/2025").is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn submission_verdicts_are_recognised() {
+        assert_eq!(
+            parse_submission("<article><p>That's the right answer! You are one gold star closer"),
+            Some(Submission::Correct)
+        );
+        assert_eq!(
+            parse_submission("<p>That's not the right answer; your answer is too high.</p>"),
+            Some(Submission::Incorrect {
+                hint: Some("too high".to_string())
+            })
+        );
+        assert_eq!(
+            parse_submission("You gave an answer too recently. You have 41s left to wait."),
+            Some(Submission::TooSoon {
+                wait: Some("41s".to_string())
+            })
+        );
+        assert_eq!(parse_submission("<html>[Log In]</html>"), None);
+        assert_eq!(form_encode("a b&1"), "a%20b%261");
+    }
+}
@@ -0,0 +1,440 @@
+use crate::parse::{find_byte, ParseError};
+use memmap2::Mmap;
+use std::borrow::Cow;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead};
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+// Puzzle input held as one UTF-8 byte buffer, owned or memory-mapped.
+//
+// Solvers borrow lines and bytes straight out of the buffer instead of receiving a `String` per line.
+pub struct Input {
+    bytes: Bytes,
+}
+
+enum Bytes {
+    Owned(Vec<u8>),
+    Mapped(Mmap),
+}
+
+impl Input {
+    // Takes raw bytes and returns them as input, or an InvalidData error if they are not UTF-8.
+    pub fn new(bytes: Vec<u8>) -> io::Result<Self> {
+        check_utf8(&bytes)?;
+        Ok(Self {
+            bytes: Bytes::Owned(bytes),
+        })
+    }
+
+    // Takes a file path, memory-maps the file, and returns it as input.
+    //
+    // Empty files are read normally since they cannot be mapped. The file must not change while mapped.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = fs::File::open(path)?;
+        if file.metadata()?.len() == 0 {
+            return Self::new(Vec::new());
+        }
+        // SAFETY: the mapping is read-only and input files are not modified while a solver runs.
+        let map = unsafe { Mmap::map(&file)? };
+        check_utf8(&map)?;
+        Ok(Self {
+            bytes: Bytes::Mapped(map),
+        })
+    }
+
+    // Takes a file path, reads the whole file into memory, and returns it as input.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        Self::new(fs::read(path)?)
+    }
+
+    // Takes lines and returns them joined with newlines as input, e.g. for tests and generated inputs.
+    pub fn from_lines<S: AsRef<str>>(lines: &[S]) -> Self {
+        let mut text = String::with_capacity(lines.iter().map(|l| l.as_ref().len() + 1).sum());
+        for line in lines {
+            text.push_str(line.as_ref());
+            text.push('\n');
+        }
+        Self::from(text)
+    }
+
+    // Returns the whole input as bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        match &self.bytes {
+            Bytes::Owned(bytes) => bytes,
+            Bytes::Mapped(map) => map,
+        }
+    }
+
+    // Returns the whole input as text.
+    pub fn as_str(&self) -> &str {
+        // SAFETY: every constructor checks that the bytes are UTF-8.
+        unsafe { std::str::from_utf8_unchecked(self.as_bytes()) }
+    }
+
+    // Returns the lines without their "\n" or "\r\n" terminators, like `str::lines`.
+    pub fn lines(&self) -> impl Iterator<Item = &str> + '_ {
+        lines(self.as_str())
+    }
+
+    // Returns the lines as bytes, without their terminators.
+    pub fn byte_lines(&self) -> ByteLines<'_> {
+        byte_lines(self.as_bytes())
+    }
+
+    // Returns the number of lines.
+    pub fn line_count(&self) -> usize {
+        self.lines().count()
+    }
+
+    // Returns the input's length in bytes.
+    pub fn len(&self) -> usize {
+        self.as_bytes().len()
+    }
+
+    // Returns whether the input has no bytes at all.
+    pub fn is_empty(&self) -> bool {
+        self.as_bytes().is_empty()
+    }
+
+    // Returns whether the input is memory-mapped rather than owned.
+    pub fn is_mapped(&self) -> bool {
+        matches!(self.bytes, Bytes::Mapped(_))
+    }
+}
+
+// Takes puzzle text and returns it normalized: without a leading byte order mark, with "\n" line
+// ends, without trailing spaces or tabs on any line, without leading or trailing blank lines, and
+// ending in exactly one "\n" unless empty.
+//
+// Text that is already normal, as fetched inputs usually are, is returned borrowed.
+pub fn normalize(text: &str) -> Cow<'_, str> {
+    if is_normal(text.as_bytes()) {
+        return Cow::Borrowed(text);
+    }
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let lines: Vec<&str> = text
+        .split('\n')
+        .map(|line| line.trim_end_matches([' ', '\t', '\r']))
+        .collect();
+    let first = lines.iter().position(|line| !line.is_empty());
+    let last = lines.iter().rposition(|line| !line.is_empty());
+    let (Some(first), Some(last)) = (first, last) else {
+        return Cow::Owned(String::new());
+    };
+    let mut normal = String::with_capacity(text.len() + 1);
+    for line in &lines[first..=last] {
+        normal.push_str(line);
+        normal.push('\n');
+    }
+    Cow::Owned(normal)
+}
+
+// Takes bytes and returns whether `normalize` would leave them unchanged.
+fn is_normal(bytes: &[u8]) -> bool {
+    match bytes {
+        [] => true,
+        [b'\n', ..] | [.., b'\n', b'\n'] | [0xef, 0xbb, 0xbf, ..] => false,
+        [.., b'\n'] => {
+            find_byte(bytes, b'\r').is_none()
+                && !bytes
+                    .windows(2)
+                    .any(|pair| pair[1] == b'\n' && matches!(pair[0], b' ' | b'\t'))
+        }
+        _ => false,
+    }
+}
+
+// How a day's puzzle input is laid out, declared by each solver and checked before it runs.
+//
+// Checks expect normalized text (see `normalize`), so blank lines only appear between sections.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputShape {
+    // Exactly one line, such as day 2's comma-separated ranges.
+    SingleLine,
+    // One item per line, with no blank lines.
+    Lines,
+    // Rows of cells with no blank lines; `ragged` rows may differ in width because the day pads them.
+    Grid { ragged: bool },
+    // Blocks of lines separated by single blank lines, with the number of blocks in this range.
+    Sections(RangeInclusive<usize>),
+}
+
+impl InputShape {
+    // Takes normalized puzzle text and returns an error naming the first line that breaks this shape.
+    pub fn check(&self, text: &str) -> Result<(), ParseError> {
+        if text.is_empty() {
+            return Err(ParseError::new("the input is empty"));
+        }
+        let mut sections = 1;
+        let mut width = None;
+        let mut previous_blank = false;
+        for (index, line) in lines(text).enumerate() {
+            let fail = |message: String| Err(ParseError::new(message).at_line(index + 1));
+            let blank = line.is_empty();
+            match self {
+                Self::SingleLine if index > 0 => {
+                    return fail("expected the input on a single line".to_string())
+                }
+                Self::Lines | Self::Grid { .. } if blank => {
+                    return fail("unexpected blank line".to_string())
+                }
+                Self::Grid { ragged: false } => match width {
+                    Some(width) if width != line.len() => {
+                        return fail(format!(
+                            "grid rows must all be {width} wide, found {}",
+                            line.len()
+                        ))
+                    }
+                    _ => width = Some(line.len()),
+                },
+                Self::Sections(_) if blank && previous_blank => {
+                    return fail("sections must be separated by a single blank line".to_string())
+                }
+                Self::Sections(_) if blank => sections += 1,
+                _ => {}
+            }
+            previous_blank = blank;
+        }
+        match self {
+            Self::Sections(range) if !range.contains(&sections) => Err(ParseError::new(format!(
+                "expected {} blank-line-separated sections, found {sections}",
+                describe_range(range)
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+// Takes a range of section counts and returns it in words, e.g. "2" or "at least 2".
+fn describe_range(range: &RangeInclusive<usize>) -> String {
+    match (range.start(), range.end()) {
+        (start, end) if start == end => start.to_string(),
+        (start, &usize::MAX) => format!("at least {start}"),
+        (start, end) => format!("{start} to {end}"),
+    }
+}
+
+// Takes text and returns its lines like `str::lines`, but split with the faster `ByteLines`.
+pub fn lines(text: &str) -> impl Iterator<Item = &str> + '_ {
+    // SAFETY: splitting UTF-8 at ASCII bytes leaves every piece valid UTF-8.
+    byte_lines(text.as_bytes()).map(|line| unsafe { std::str::from_utf8_unchecked(line) })
+}
+
+// Takes bytes and returns their lines without terminators; see `Input::byte_lines`.
+pub fn byte_lines(bytes: &[u8]) -> ByteLines<'_> {
+    ByteLines { rest: bytes }
+}
+
+// Iterator over the lines of an `Input` as bytes; see `Input::byte_lines`.
+pub struct ByteLines<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> Iterator for ByteLines<'a> {
+    type Item = &'a [u8];
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a [u8]> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        match find_byte(self.rest, b'\n') {
+            Some(end) => {
+                let line = &self.rest[..end];
+                self.rest = &self.rest[end + 1..];
+                Some(line.strip_suffix(b"\r").unwrap_or(line))
+            }
+            None => Some(std::mem::take(&mut self.rest)),
+        }
+    }
+}
+
+impl Input {
+    // Returns the input normalized as by `normalize`, keeping it mapped when it already was normal.
+    pub fn normalized(self) -> Self {
+        match normalize(self.as_str()) {
+            Cow::Borrowed(_) => self,
+            Cow::Owned(text) => Self::from(text),
+        }
+    }
+}
+
+impl From<String> for Input {
+    fn from(text: String) -> Self {
+        Self {
+            bytes: Bytes::Owned(text.into_bytes()),
+        }
+    }
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Self {
+        Self::from(text.to_string())
+    }
+}
+
+impl fmt::Debug for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Input")
+            .field("len", &self.len())
+            .field("mapped", &self.is_mapped())
+            .finish()
+    }
+}
+
+// Takes a reader and a callback, and calls it with each line number and line (without "\n" or "\r\n") while
+// reusing one buffer; stops at the first line the callback rejects and returns its error as InvalidData.
+//
+// Used by `Solution::stream_input` so inputs never have to fit in memory at once. Lines are
+// normalized one at a time like `normalize` does: the byte order mark and trailing whitespace are
+// dropped, but blank lines are passed on.
+pub fn for_each_line(
+    reader: &mut dyn BufRead,
+    mut f: impl FnMut(usize, &str) -> Result<(), ParseError>,
+) -> io::Result<()> {
+    let mut line = String::new();
+    let mut first = true;
+    for number in 1.. {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let mut text = line.trim_end_matches([' ', '\t', '\r', '\n']);
+        if std::mem::take(&mut first) {
+            text = text.strip_prefix('\u{feff}').unwrap_or(text);
+        }
+        f(number, text)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.at_line(number)))?;
+    }
+    Ok(())
+}
+
+// Takes bytes and returns an InvalidData error if they are not UTF-8.
+fn check_utf8(bytes: &[u8]) -> io::Result<()> {
+    std::str::from_utf8(bytes).map(|_| ()).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("input is not UTF-8: {e}"),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_lines_like_str_lines() {
+        for text in ["", "\n", "a\r", "a\r\n\r\n", "L68\r\nR48\n\n7", "x\ny\n"] {
+            let input = Input::from(text);
+            assert_eq!(
+                input.lines().collect::<Vec<_>>(),
+                text.lines().collect::<Vec<_>>(),
+                "{text:?}"
+            );
+        }
+        let input = Input::from("L68\r\nR48\n\n7");
+        assert_eq!(
+            input.lines().collect::<Vec<_>>(),
+            vec!["L68", "R48", "", "7"]
+        );
+        assert_eq!(input.byte_lines().next(), Some(&b"L68"[..]));
+        assert_eq!(input.line_count(), 4);
+        assert_eq!(Input::from_lines(&["a", "b"]).as_str(), "a\nb\n");
+    }
+
+    #[test]
+    fn streams_the_same_lines() {
+        let text = "L68\r\nR48\n\n7";
+        let mut streamed = Vec::new();
+        for_each_line(&mut text.as_bytes(), |_, line| {
+            streamed.push(line.to_string());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(streamed, Input::from(text).lines().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn normalizes_byte_order_marks_line_ends_and_whitespace() {
+        for (text, want) in [
+            ("", ""),
+            ("\n\n", ""),
+            ("L68\nR48", "L68\nR48\n"),
+            ("\u{feff}L68\r\nR48 \t\r\n\r\n", "L68\nR48\n"),
+            ("\n  \n1-3\n\n  7\n\n\n", "1-3\n\n  7\n"),
+        ] {
+            assert_eq!(normalize(text), want, "{text:?}");
+        }
+        assert!(matches!(normalize("12 \n\n 3\n"), Cow::Owned(_)));
+        assert!(matches!(normalize("12\n\n 3\n"), Cow::Borrowed(_)));
+        assert_eq!(Input::from("a\r\nb").normalized().as_str(), "a\nb\n");
+
+        let mut streamed = Vec::new();
+        let text = "\u{feff}L68 \r\n\nR48\t";
+        for_each_line(&mut text.as_bytes(), |_, line| {
+            streamed.push(line.to_string());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(streamed, vec!["L68", "", "R48"]);
+
+        let err = for_each_line(&mut "a\nb\n".as_bytes(), |_, line| match line {
+            "b" => Err(ParseError::new("no b")),
+            _ => Ok(()),
+        })
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "line 2: no b");
+    }
+
+    #[test]
+    fn checks_declared_input_shapes() {
+        let line = |shape: &InputShape, text: &str| shape.check(text).map_err(|e| e.line());
+        assert_eq!(line(&InputShape::SingleLine, "1-2,3-4\n"), Ok(()));
+        assert_eq!(line(&InputShape::SingleLine, "1-2\n3-4\n"), Err(Some(2)));
+        assert_eq!(line(&InputShape::Lines, "L1\n\nR2\n"), Err(Some(2)));
+        assert_eq!(
+            line(&InputShape::Grid { ragged: false }, "@.\n.@\n"),
+            Ok(())
+        );
+        assert_eq!(
+            line(&InputShape::Grid { ragged: false }, "@.\n.\n"),
+            Err(Some(2))
+        );
+        assert_eq!(line(&InputShape::Grid { ragged: true }, "@.\n.\n"), Ok(()));
+
+        let two = InputShape::Sections(2..=2);
+        assert_eq!(line(&two, "1-3\n\n7\n"), Ok(()));
+        assert_eq!(line(&two, "1-3\n\n\n7\n"), Err(Some(3)));
+        let error = two.check("1-3\n7\n").unwrap_err();
+        assert_eq!(
+            error.message(),
+            "expected 2 blank-line-separated sections, found 1"
+        );
+        assert!(InputShape::Lines.check("").is_err());
+    }
+
+    #[test]
+    fn maps_files_and_rejects_invalid_utf8() {
+        let dir = std::env::temp_dir().join(format!("aoc2025-input-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("day01.txt");
+        fs::write(&path, "L68\nR48\n").unwrap();
+        let mapped = Input::open(&path).unwrap();
+        assert!(mapped.is_mapped());
+        assert_eq!(mapped.lines().collect::<Vec<_>>(), vec!["L68", "R48"]);
+        assert_eq!(Input::read(&path).unwrap().as_bytes(), mapped.as_bytes());
+
+        fs::write(&path, "").unwrap();
+        assert!(Input::open(&path).unwrap().is_empty());
+
+        fs::write(&path, [0xff, b'\n']).unwrap();
+        let err = Input::open(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(Input::new(vec![0xc3]).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,550 @@
+use std::collections::BTreeSet;
+use std::io::{self, Write};
+
+// Seeded synthetic puzzle inputs, for benchmarking without the real (private) inputs.
+//
+// Every generator produces input in the day's exact format and is deterministic for a given
+// size and seed. `size` counts each day's natural unit (lines, ranges, points, machines, ...);
+// `default_size` approximates the real input so timings are comparable.
+
+pub const DEFAULT_SEED: u64 = 2025;
+
+// Small deterministic PRNG (SplitMix64); quality is plenty for test data and it needs no dependency.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Takes inclusive bounds and returns a uniformly chosen value between them.
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next_u64() % (high - low + 1)
+    }
+
+    // Takes a probability and returns true with that chance.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) < p * (1u64 << 53) as f64
+    }
+}
+
+// Takes a day and returns the generator size that roughly matches a real input, if the day has a generator.
+pub fn default_size(day: u32) -> Option<usize> {
+    match day {
+        1 => Some(4_500),
+        2 => Some(40),
+        3 => Some(200),
+        4 => Some(140),
+        5 => Some(190),
+        6 => Some(1_000),
+        7 => Some(141),
+        8 => Some(1_000),
+        9 => Some(500),
+        10 => Some(180),
+        11 => Some(600),
+        12 => Some(1_000),
+        _ => None,
+    }
+}
+
+// Takes a day, size and seed and returns generated input lines, or None if the day has no generator.
+pub fn generate(day: u32, size: usize, seed: u64) -> Option<Vec<String>> {
+    let mut rng = Rng::new(seed ^ u64::from(day).wrapping_mul(0xA24B_AED4_963E_E407));
+    let size = size.max(1);
+    let lines = match day {
+        1 | 3 | 5 => streamed_lines(day, &mut rng, size)?.collect(),
+        2 => day02(&mut rng, size),
+        4 => day04(&mut rng, size),
+        6 => day06(&mut rng, size),
+        7 => day07(&mut rng, size),
+        8 => day08(&mut rng, size),
+        9 => day09(&mut rng, size),
+        10 => day10(&mut rng, size),
+        11 => day11(&mut rng, size),
+        12 => day12(&mut rng, size),
+        _ => return None,
+    };
+    Some(lines)
+}
+
+// Takes a day, size, seed and writer, writes the same input as `generate` one line at a time, and returns false for days that need the whole input built first.
+//
+// Lets the streaming days be stress-tested on inputs larger than memory.
+pub fn write_streamed(day: u32, size: usize, seed: u64, out: &mut dyn Write) -> io::Result<bool> {
+    let mut rng = Rng::new(seed ^ u64::from(day).wrapping_mul(0xA24B_AED4_963E_E407));
+    let Some(lines) = streamed_lines(day, &mut rng, size.max(1)) else {
+        return Ok(false);
+    };
+    for line in lines {
+        out.write_all(line.as_bytes())?;
+        out.write_all(b"\n")?;
+    }
+    Ok(true)
+}
+
+// Takes a day, generator and size, and returns the day's lines as a lazy iterator for days generated line by line.
+fn streamed_lines(
+    day: u32,
+    rng: &mut Rng,
+    size: usize,
+) -> Option<Box<dyn Iterator<Item = String> + '_>> {
+    match day {
+        1 => Some(Box::new(day01(rng, size))),
+        3 => Some(Box::new(day03(rng, size))),
+        5 => Some(Box::new(day05(rng, size))),
+        _ => None,
+    }
+}
+
+// `size` dial rotations such as "L68" or "R402".
+fn day01(rng: &mut Rng, size: usize) -> impl Iterator<Item = String> + '_ {
+    (0..size).map(|_| {
+        let direction = if rng.chance(0.5) { 'L' } else { 'R' };
+        format!("{direction}{}", rng.range(1, 999))
+    })
+}
+
+// One line of `size` comma-separated, non-overlapping ID ranges of up to ten digits.
+fn day02(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut starts = BTreeSet::new();
+    while starts.len() < size {
+        let digits = rng.range(1, 10) as u32;
+        starts.insert(rng.range(10u64.pow(digits - 1), 10u64.pow(digits) - 1));
+    }
+
+    let starts: Vec<u64> = starts.into_iter().collect();
+    let ranges: Vec<String> = starts
+        .iter()
+        .enumerate()
+        .map(|(index, &start)| {
+            let limit = starts.get(index + 1).map_or(u64::MAX, |next| next - 1);
+            let end = (start + rng.range(0, 100_000))
+                .min(limit)
+                .min(9_999_999_999);
+            format!("{start}-{end}")
+        })
+        .collect();
+    vec![ranges.join(",")]
+}
+
+// `size` battery banks of 100 digits from 1 to 9.
+fn day03(rng: &mut Rng, size: usize) -> impl Iterator<Item = String> + '_ {
+    (0..size).map(|_| {
+        (0..100)
+            .map(|_| char::from(b'0' + rng.range(1, 9) as u8))
+            .collect()
+    })
+}
+
+// A `size` x `size` grid where about 60% of cells hold a roll ('@').
+fn day04(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| if rng.chance(0.6) { '@' } else { '.' })
+                .collect()
+        })
+        .collect()
+}
+
+// `size` fresh ID ranges, a blank line, then five times as many available IDs.
+fn day05(rng: &mut Rng, size: usize) -> impl Iterator<Item = String> + '_ {
+    const MAX_ID: u64 = 500_000_000_000_000;
+
+    (0..size * 6 + 1).map(move |index| {
+        if index < size {
+            let start = rng.range(1, MAX_ID);
+            let end = (start + rng.range(0, MAX_ID / 50)).min(MAX_ID);
+            format!("{start}-{end}")
+        } else if index == size {
+            String::new()
+        } else {
+            rng.range(1, MAX_ID).to_string()
+        }
+    })
+}
+
+// A worksheet of `size` problems: four rows of up to four-digit numbers over an operator row.
+fn day06(rng: &mut Rng, size: usize) -> Vec<String> {
+    const ROWS: usize = 4;
+
+    let mut lines = vec![String::new(); ROWS + 1];
+    for problem in 0..size {
+        let numbers: Vec<String> = (0..ROWS)
+            .map(|_| {
+                let digits = rng.range(1, 4) as u32;
+                rng.range(10u64.pow(digits - 1), 10u64.pow(digits) - 1)
+                    .to_string()
+            })
+            .collect();
+        let width = numbers.iter().map(String::len).max().unwrap_or(1);
+
+        for (line, number) in lines.iter_mut().zip(&numbers) {
+            if problem > 0 {
+                line.push(' ');
+            }
+            if rng.chance(0.5) {
+                line.push_str(&format!("{number:<width$}"));
+            } else {
+                line.push_str(&format!("{number:>width$}"));
+            }
+        }
+        let operator = if rng.chance(0.5) { '+' } else { '*' };
+        let line = &mut lines[ROWS];
+        if problem > 0 {
+            line.push(' ');
+        }
+        line.push_str(&format!("{operator:<width$}"));
+    }
+    lines
+}
+
+// A manifold `size` columns wide and 142 rows tall, with splitters on every other row inside the beam's cone.
+//
+// The height stays fixed so the number of timelines in part 2 cannot overflow as the width grows.
+fn day07(rng: &mut Rng, size: usize) -> Vec<String> {
+    const ROWS: usize = 142;
+
+    let width = size.max(3);
+    let start = width / 2;
+    (0..ROWS)
+        .map(|row| {
+            (0..width)
+                .map(|col| {
+                    if row == 0 {
+                        if col == start {
+                            'S'
+                        } else {
+                            '.'
+                        }
+                    } else if row % 2 == 0
+                        && col.abs_diff(start) < row / 2
+                        && col.abs_diff(start) % 2 == (row / 2 + 1) % 2
+                        && rng.chance(0.5)
+                    {
+                        '^'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// `size` junction boxes with coordinates below 100,000.
+fn day08(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{},{},{}",
+                rng.range(0, 99_999),
+                rng.range(0, 99_999),
+                rng.range(0, 99_999)
+            )
+        })
+        .collect()
+}
+
+// About `size` red tiles forming a simple rectilinear polygon: a random skyline above y = 50,000 and one below.
+fn day09(rng: &mut Rng, size: usize) -> Vec<String> {
+    let columns = (size / 4).max(2);
+    let step = (96_000 / columns as u64).max(2);
+    let xs: Vec<u64> = (0..=columns)
+        .map(|column| 2_000 + column as u64 * step + rng.range(0, step / 2))
+        .collect();
+
+    let mut tiles = Vec::with_capacity(columns * 4 + 4);
+    let mut top = rng.range(50_001, 98_000);
+    tiles.push((xs[0], top));
+    for &x in &xs[1..] {
+        tiles.push((x, top));
+        if x != xs[columns] {
+            let next = rng.range(50_001, 98_000);
+            top = if next == top { next + 1 } else { next };
+            tiles.push((x, top));
+        }
+    }
+
+    let mut bottom = rng.range(2_000, 49_999);
+    tiles.push((xs[columns], bottom));
+    for &x in xs[..columns].iter().rev() {
+        tiles.push((x, bottom));
+        if x != xs[0] {
+            let next = rng.range(2_000, 49_999);
+            bottom = if next == bottom { next - 1 } else { next };
+            tiles.push((x, bottom));
+        }
+    }
+
+    tiles.iter().map(|(x, y)| format!("{x},{y}")).collect()
+}
+
+// `size` machines whose light pattern and joltage targets come from random button presses, so every machine is solvable.
+fn day10(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0..size)
+        .map(|_| {
+            let lights = rng.range(4, 10) as usize;
+            let button_count = rng.range(lights as u64 - 2, lights as u64 + 3) as usize;
+            let buttons: Vec<Vec<usize>> = (0..button_count)
+                .map(|_| {
+                    let wires = rng.range(1, lights as u64 - 1) as usize;
+                    let mut wired = BTreeSet::new();
+                    while wired.len() < wires {
+                        wired.insert(rng.range(0, lights as u64 - 1) as usize);
+                    }
+                    wired.into_iter().collect()
+                })
+                .collect();
+
+            let mut pattern = vec![false; lights];
+            let mut joltage = vec![0u64; lights];
+            for button in &buttons {
+                let presses = rng.range(0, 20);
+                for &light in button {
+                    joltage[light] += presses;
+                    pattern[light] ^= presses % 2 == 1;
+                }
+            }
+
+            let pattern: String = pattern
+                .iter()
+                .map(|&on| if on { '#' } else { '.' })
+                .collect();
+            let buttons: Vec<String> = buttons
+                .iter()
+                .map(|wired| {
+                    let wired: Vec<String> = wired.iter().map(ToString::to_string).collect();
+                    format!("({})", wired.join(","))
+                })
+                .collect();
+            let joltage: Vec<String> = joltage.iter().map(ToString::to_string).collect();
+            format!(
+                "[{pattern}] {} {{{}}}",
+                buttons.join(" "),
+                joltage.join(",")
+            )
+        })
+        .collect()
+}
+
+// A layered device graph of about `size` nodes from svr and you to out, passing dac and fft layers.
+//
+// Edges only go to the next layer and the depth is capped, which keeps path counts within u64.
+fn day11(rng: &mut Rng, size: usize) -> Vec<String> {
+    const MAX_LAYERS: usize = 36;
+
+    let layers = (size / 8).clamp(4, MAX_LAYERS);
+    let width = (size / layers).max(2);
+
+    let mut used: BTreeSet<String> = ["svr", "you", "dac", "fft", "out"]
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    let mut name = |rng: &mut Rng| loop {
+        let candidate: String = (0..3)
+            .map(|_| char::from(b'a' + rng.range(0, 25) as u8))
+            .collect();
+        if used.insert(candidate.clone()) {
+            return candidate;
+        }
+    };
+
+    let mut grid: Vec<Vec<String>> = (0..layers)
+        .map(|_| (0..width).map(|_| name(rng)).collect())
+        .collect();
+    grid[0][0] = "svr".to_string();
+    grid[1][0] = "you".to_string();
+    grid[layers / 3][width - 1] = "dac".to_string();
+    grid[2 * layers / 3][0] = "fft".to_string();
+
+    let mut lines = Vec::with_capacity(layers * width);
+    for (layer, nodes) in grid.iter().enumerate() {
+        let Some(next) = grid.get(layer + 1) else {
+            lines.extend(nodes.iter().map(|node| format!("{node}: out")));
+            break;
+        };
+
+        let mut edges: Vec<BTreeSet<usize>> = nodes
+            .iter()
+            .map(|_| {
+                let degree = (rng.range(1, 3) as usize).min(next.len());
+                let mut picked = BTreeSet::new();
+                while picked.len() < degree {
+                    picked.insert(rng.range(0, next.len() as u64 - 1) as usize);
+                }
+                picked
+            })
+            .collect();
+        // Every node needs a way in, or dac/fft could end up unreachable.
+        for target in 0..next.len() {
+            if !edges.iter().any(|picked| picked.contains(&target)) {
+                let source = rng.range(0, nodes.len() as u64 - 1) as usize;
+                edges[source].insert(target);
+            }
+        }
+
+        for (node, picked) in nodes.iter().zip(&edges) {
+            let outputs: Vec<&str> = picked.iter().map(|&index| next[index].as_str()).collect();
+            lines.push(format!("{node}: {}", outputs.join(" ")));
+        }
+    }
+    lines
+}
+
+// Six 3x3 present shapes followed by `size` large regions whose demand is 70-110% of their area.
+fn day12(rng: &mut Rng, size: usize) -> Vec<String> {
+    const SHAPES: usize = 6;
+
+    let mut lines = Vec::new();
+    let mut areas = Vec::with_capacity(SHAPES);
+    for index in 0..SHAPES {
+        lines.push(format!("{index}:"));
+        let mut cells = [[true; 3]; 3];
+        for _ in 0..rng.range(1, 3) {
+            let (row, col) = (rng.range(0, 2) as usize, rng.range(0, 2) as usize);
+            if (row, col) != (1, 1) {
+                cells[row][col] = false;
+            }
+        }
+        areas.push(cells.iter().flatten().filter(|&&cell| cell).count() as u64);
+        for row in cells {
+            lines.push(
+                row.iter()
+                    .map(|&cell| if cell { '#' } else { '.' })
+                    .collect(),
+            );
+        }
+        lines.push(String::new());
+    }
+
+    for _ in 0..size {
+        let (width, height) = (rng.range(35, 50), rng.range(35, 50));
+        let budget = width * height * rng.range(70, 110) / 100;
+        let mut counts = [0u64; SHAPES];
+        let mut used = 0;
+        loop {
+            let shape = rng.range(0, SHAPES as u64 - 1) as usize;
+            if used + areas[shape] > budget {
+                break;
+            }
+            used += areas[shape];
+            counts[shape] += 1;
+        }
+        let counts: Vec<String> = counts.iter().map(ToString::to_string).collect();
+        lines.push(format!("{width}x{height}: {}", counts.join(" ")));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use crate::input::Input;
+
+    #[test]
+    fn generation_is_deterministic_per_seed() {
+        for day in days::implemented_days() {
+            let a = generate(day, 20, 7).unwrap();
+            assert_eq!(a, generate(day, 20, 7).unwrap(), "day {day}");
+            assert_ne!(a, generate(day, 20, 8).unwrap(), "day {day}");
+        }
+        assert!(generate(13, 20, 7).is_none());
+    }
+
+    #[test]
+    fn every_day_solves_generated_input() {
+        for day in days::implemented_days() {
+            let lines = generate(day, 60, DEFAULT_SEED).unwrap();
+            let mut solver = days::make_solver(day).unwrap();
+            solver.set_input(&Input::from_lines(&lines)).unwrap();
+            for part in solver.declared_parts() {
+                assert!(!solver.solve_part(part.number).is_empty(), "day {day}");
+            }
+        }
+    }
+
+    #[test]
+    fn generated_input_round_trips_through_each_days_model() {
+        for day in days::implemented_days() {
+            let text = Input::from_lines(&generate(day, 60, DEFAULT_SEED).unwrap());
+            let reformatted = days::reformat_input(day, text.as_str()).unwrap();
+            assert_eq!(reformatted.as_deref(), Ok(text.as_str()), "day {day}");
+            assert!(
+                days::dump_parsed(day, text.as_str()).unwrap().is_ok(),
+                "day {day}"
+            );
+            let shape = days::make_solver(day).unwrap().input_shape();
+            assert_eq!(shape.check(text.as_str()), Ok(()), "day {day}");
+        }
+        assert!(days::reformat_input(13, "").is_none());
+        assert!(days::dump_parsed(13, "").is_none());
+    }
+
+    #[test]
+    fn streaming_days_match_stored_input() {
+        for day in days::streaming_days() {
+            let lines = generate(day, 60, DEFAULT_SEED).unwrap();
+            let mut text = Vec::new();
+            assert!(write_streamed(day, 60, DEFAULT_SEED, &mut text).unwrap());
+            assert_eq!(text, Input::from_lines(&lines).as_bytes(), "day {day}");
+
+            let mut stored = days::make_solver(day).unwrap();
+            stored.set_input(&Input::from_lines(&lines)).unwrap();
+            let mut streamed = days::make_solver(day).unwrap();
+            streamed.stream_input(&mut text.as_slice()).unwrap();
+            for part in [1, 2] {
+                assert_eq!(
+                    streamed.solve_part(part),
+                    stored.solve_part(part),
+                    "day {day}"
+                );
+            }
+        }
+
+        let mut solver = days::make_solver(8).unwrap();
+        let err = solver.stream_input(&mut &b"1,2,3\n"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+        assert!(!write_streamed(8, 10, DEFAULT_SEED, &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn polygon_edges_are_axis_aligned() {
+        let lines = generate(9, 100, DEFAULT_SEED).unwrap();
+        let tiles: Vec<(u64, u64)> = lines
+            .iter()
+            .map(|line| {
+                let (x, y) = line.split_once(',').unwrap();
+                (x.parse().unwrap(), y.parse().unwrap())
+            })
+            .collect();
+        for (index, &(x, y)) in tiles.iter().enumerate() {
+            let (nx, ny) = tiles[(index + 1) % tiles.len()];
+            assert!(x == nx || y == ny, "tile {index}");
+            assert_ne!((x, y), (nx, ny), "tile {index}");
+        }
+    }
+
+    #[test]
+    fn day11_graph_has_every_named_node() {
+        let lines = generate(11, 100, DEFAULT_SEED).unwrap();
+        for node in ["svr:", "you:", "dac:", "fft:"] {
+            assert!(lines.iter().any(|line| line.starts_with(node)), "{node}");
+        }
+        assert!(lines.iter().any(|line| line.ends_with(" out")));
+
+        let mut solver = days::make_solver(11).unwrap();
+        solver.set_input(&Input::from_lines(&lines)).unwrap();
+        assert_ne!(solver.solve_part(2), "0");
+    }
+}
@@ -0,0 +1,290 @@
+use crate::aocnet::{self, Client};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// AoC asks clients not to request a private leaderboard more than once every 15 minutes.
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    #[serde(default)]
+    pub owner_id: u64,
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub stars: u32,
+    #[serde(default)]
+    pub local_score: u64,
+    #[serde(default)]
+    pub last_star_ts: u64,
+    // Day -> part -> star, keyed by the numeric strings AoC uses.
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u32, BTreeMap<u32, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    pub get_star_ts: u64,
+    #[serde(default)]
+    pub star_index: u64,
+}
+
+impl Member {
+    // Returns the member's display name, matching AoC's label for anonymous users.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) if !name.is_empty() => name.clone(),
+            _ => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    // Takes a day and part and returns the Unix time the star was earned, if it was.
+    pub fn star_time(&self, day: u32, part: u32) -> Option<u64> {
+        self.completion_day_level
+            .get(&day)?
+            .get(&part)
+            .map(|star| star.get_star_ts)
+    }
+}
+
+impl Leaderboard {
+    // Takes the leaderboard JSON text and returns the parsed leaderboard.
+    pub fn parse(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    // Returns the event year, falling back to the crate's year if the event field is not numeric.
+    fn year(&self) -> u32 {
+        self.event.trim().parse().unwrap_or(aocnet::YEAR)
+    }
+
+    // Returns members ordered by local score, then stars, then who got their last star first.
+    pub fn standings(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+        members
+    }
+
+    // Renders the overall standings with one star column per puzzle day and returns the table text.
+    pub fn render_standings(&self) -> String {
+        let days = aocnet::puzzle_count(self.year());
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            "Private leaderboard {} ({} members)",
+            self.event,
+            self.members.len()
+        );
+        let _ = write!(out, "{:>4} {:>5} ", "", "Score");
+        for day in 1..=days {
+            let _ = write!(out, "{day:>3}");
+        }
+        out.push('\n');
+
+        for (rank, member) in self.standings().into_iter().enumerate() {
+            let _ = write!(out, "{:>3}) {:>5} ", rank + 1, member.local_score);
+            for day in 1..=days {
+                let glyph = match (member.star_time(day, 1), member.star_time(day, 2)) {
+                    (Some(_), Some(_)) => '*',
+                    (Some(_), None) => '+',
+                    _ => '.',
+                };
+                let _ = write!(out, "{glyph:>3}");
+            }
+            let _ = writeln!(out, "  {} ({}*)", member.display_name(), member.stars);
+        }
+
+        out
+    }
+
+    // Takes a day and renders each member's star times after unlock plus the part 1 to part 2 delta.
+    pub fn render_day(&self, day: u32) -> String {
+        let unlock = aocnet::unlock_time(self.year(), day)
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO)
+            .as_secs();
+        let since_unlock = |ts: Option<u64>| -> String {
+            ts.map(|ts| aocnet::format_countdown(Duration::from_secs(ts.saturating_sub(unlock))))
+                .unwrap_or_else(|| "-".to_string())
+        };
+
+        let mut finishers: Vec<(&Member, Option<u64>, Option<u64>)> = self
+            .members
+            .values()
+            .map(|member| (member, member.star_time(day, 1), member.star_time(day, 2)))
+            .filter(|(_, part1, _)| part1.is_some())
+            .collect();
+        finishers.sort_by_key(|&(member, part1, part2)| (part2.is_none(), part2, part1, member.id));
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "Day {day} ({} of {} members with stars)",
+            finishers.len(),
+            self.members.len()
+        );
+        let _ = writeln!(
+            out,
+            "{:>4} {:>12} {:>12} {:>12}  Name",
+            "", "Part 1", "Part 2", "Delta"
+        );
+        for (rank, (member, part1, part2)) in finishers.into_iter().enumerate() {
+            let delta = match (part1, part2) {
+                (Some(p1), Some(p2)) => {
+                    aocnet::format_countdown(Duration::from_secs(p2.saturating_sub(p1)))
+                }
+                _ => "-".to_string(),
+            };
+            let _ = writeln!(
+                out,
+                "{:>3}) {:>12} {:>12} {:>12}  {}",
+                rank + 1,
+                since_unlock(part1),
+                since_unlock(part2),
+                delta,
+                member.display_name()
+            );
+        }
+
+        out
+    }
+}
+
+// Takes a leaderboard ID and returns where its JSON is cached.
+pub fn cache_path(id: &str) -> PathBuf {
+    PathBuf::from("input").join(format!("leaderboard-{id}.json"))
+}
+
+// Takes a cache file's modification time and the current time, and returns whether it is recent enough to reuse.
+fn is_fresh(modified: SystemTime, now: SystemTime) -> bool {
+    now.duration_since(modified)
+        .map(|age| age < REFRESH_INTERVAL)
+        .unwrap_or(true)
+}
+
+// Takes a client, leaderboard ID and cache path, fetches at most once per 15 minutes, and returns the leaderboard.
+pub fn load(client: &Client, id: &str, cache: &Path) -> Result<Leaderboard, Box<dyn Error>> {
+    let modified = fs::metadata(cache).and_then(|meta| meta.modified()).ok();
+    if modified.is_some_and(|modified| is_fresh(modified, SystemTime::now())) {
+        return Ok(Leaderboard::parse(&fs::read_to_string(cache)?)?);
+    }
+
+    match client.fetch_leaderboard(id) {
+        Ok(json) => {
+            let leaderboard = Leaderboard::parse(&json)?;
+            if let Some(parent) = cache.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(cache, &json)?;
+            Ok(leaderboard)
+        }
+        Err(e) if modified.is_some() => {
+            eprintln!("Warning: leaderboard fetch failed ({e}); showing the cached copy.");
+            Ok(Leaderboard::parse(&fs::read_to_string(cache)?)?)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aocnet::ClientOptions;
+    use crate::fakeaoc::{FakeAoc, Fixtures};
+
+    const FIXTURE: &str = r#"{
+  "event": "2025",
+  "owner_id": 1,
+  "members": {
+    "1": {"id": 1, "name": "Alice", "stars": 3, "local_score": 6, "last_star_ts": 1764653700,
+          "completion_day_level": {
+            "1": {"1": {"get_star_ts": 1764565500, "star_index": 10},
+                  "2": {"get_star_ts": 1764566100, "star_index": 20}},
+            "2": {"1": {"get_star_ts": 1764653700, "star_index": 30}}}},
+    "2": {"id": 2, "name": null, "stars": 1, "local_score": 1, "last_star_ts": 1764569000,
+          "completion_day_level": {"1": {"1": {"get_star_ts": 1764569000, "star_index": 40}}}},
+    "3": {"id": 3, "name": "Carol", "stars": 0, "local_score": 0, "last_star_ts": 0,
+          "completion_day_level": {}}
+  }
+}"#;
+
+    #[test]
+    fn standings_are_ordered_by_local_score() {
+        let board = Leaderboard::parse(FIXTURE).unwrap();
+        let names: Vec<String> = board.standings().iter().map(|m| m.display_name()).collect();
+        assert_eq!(names, vec!["Alice", "(anonymous user #2)", "Carol"]);
+
+        let table = board.render_standings();
+        let alice = table.lines().find(|l| l.contains("Alice")).unwrap();
+        assert!(alice.starts_with("  1)     6   *  +  ."));
+    }
+
+    #[test]
+    fn day_view_shows_times_since_unlock_and_delta() {
+        let board = Leaderboard::parse(FIXTURE).unwrap();
+        let day1 = board.render_day(1);
+        let alice = day1.lines().find(|l| l.contains("Alice")).unwrap();
+        assert_eq!(alice, "  1)     00:05:00     00:15:00     00:10:00  Alice");
+        let anon = day1.lines().find(|l| l.contains("anonymous")).unwrap();
+        assert!(anon.contains("01:03:20            -            -"));
+        assert!(!day1.contains("Carol"));
+    }
+
+    #[test]
+    fn cache_expires_after_fifteen_minutes() {
+        let now = UNIX_EPOCH + Duration::from_secs(10_000);
+        assert!(is_fresh(now - Duration::from_secs(14 * 60), now));
+        assert!(!is_fresh(now - Duration::from_secs(15 * 60), now));
+    }
+
+    #[test]
+    fn loads_from_fixture_server_and_caches() {
+        let mut fixtures = Fixtures::default();
+        fixtures
+            .leaderboards
+            .insert("42".to_string(), FIXTURE.to_string());
+        let site = FakeAoc::start(fixtures).unwrap();
+        let dir = std::env::temp_dir().join(format!("aoc2025-lb-{}", std::process::id()));
+        let options = ClientOptions {
+            contact: Some("test".to_string()),
+            min_interval: Duration::ZERO,
+            request_log: dir.join("requests.log"),
+            base_url: site.base_url(),
+            ..ClientOptions::default()
+        };
+        let client = Client::new("fake-session", options);
+        let cache = dir.join("leaderboard-42.json");
+
+        let board = load(&client, "42", &cache).unwrap();
+        assert_eq!(board.members.len(), 3);
+        let requests = site.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].path, "/2025/leaderboard/private/view/42.json");
+
+        // A fresh cache must be served without another request, even once the site is gone.
+        drop(site);
+        let again = load(&client, "42", &cache).unwrap();
+        assert_eq!(again.members.len(), 3);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
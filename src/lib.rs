@@ -1,2 +1,18 @@
+pub mod allocstats;
+pub mod answers;
 pub mod aocnet;
+pub mod benchmark;
+pub mod cache;
+pub mod config;
 pub mod days;
+#[cfg(any(test, feature = "fake-aoc"))]
+pub mod fakeaoc;
+pub mod input;
+pub mod inputgen;
+pub mod leaderboard;
+pub mod parallel;
+pub mod parse;
+pub mod solve;
+pub mod stats;
+
+pub use solve::{solve, SolveOptions, SolveReport};
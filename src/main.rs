@@ -1,31 +1,247 @@
-use aoc2025::{aocnet, days};
-use clap::Parser;
+use aoc2025::allocstats::{self, AllocStats};
+use aoc2025::answers::{AnswerStore, DayAnswers};
+use aoc2025::benchmark::{self, Baseline, MachineInfo};
+use aoc2025::cache;
+use aoc2025::config::{Config, Session};
+use aoc2025::days::PartInfo;
+use aoc2025::input::Input;
+use aoc2025::stats::PersonalStats;
+use aoc2025::{aocnet, days, inputgen, leaderboard, parallel, solve, SolveOptions};
+use clap::{CommandFactory, Parser, Subcommand};
+use rayon::prelude::*;
 use serde::Deserialize;
 use std::collections::BTreeMap;
 use std::fs;
-use std::io::{self, BufRead, BufReader, Write};
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 
 const PROBLEMS_YAML: &str = include_str!("../problems.yaml");
 
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static ALLOCATOR: allocstats::CountingAllocator = allocstats::CountingAllocator;
+
 #[derive(Parser, Debug)]
 #[command(name = "aoc2025")]
 struct Args {
-    /// Day number (1..25)
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Day number (1..25). Defaults to today's puzzle while the event is running.
     #[arg(long)]
-    day: u32,
+    day: Option<u32>,
 
-    /// Fetch from AoC and cache into input/dayXX.txt
+    /// Fetch from AoC and cache into input/dayXX.txt (skipped if already cached)
     #[arg(long)]
     fetch: bool,
 
-    /// Run only one part (1 or 2). If omitted, runs both.
+    /// Download the input again even if cached; keeps the old file as dayXX.txt.bak and shows a diff
+    #[arg(long)]
+    refetch: bool,
+
+    /// If the puzzle is still locked, wait for it to unlock, then fetch the input and run the day
     #[arg(long)]
-    part: Option<u8>,
+    wait: bool,
+
+    /// Read the puzzle input from this file instead of input/dayXX.txt
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["fetch", "refetch", "wait"])]
+    input: Option<PathBuf>,
+
+    /// Feed the input to the solver line by line instead of loading it whole (days 1, 3 and 5), for inputs larger than memory
+    #[arg(long, conflicts_with_all = ["fetch", "refetch", "wait"])]
+    stream: bool,
+
+    /// Print the day's parsed input model as JSON instead of solving, to check what the parser saw
+    #[arg(long, conflicts_with_all = ["stream", "part", "time"])]
+    dump_parsed: bool,
+
+    /// Run only this part, by number or name (see --list-parts). If omitted, runs every part the day declares.
+    #[arg(long, value_name = "PART")]
+    part: Option<String>,
+
+    /// List the parts the day declares, with their numbers, names and descriptions, and exit
+    #[arg(long, conflicts_with_all = ["part", "dump_parsed"])]
+    list_parts: bool,
+
+    /// Run the day's parts at the same time (allocations are not counted per part then)
+    #[arg(long, conflicts_with = "part")]
+    parallel_parts: bool,
+
+    /// Run this registered variant of the selected parts instead of their default implementation (see --list-parts).
+    /// Variants need the stored input, so this cannot be combined with --stream.
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["dump_parsed", "list_parts", "stream"])]
+    variant: Option<String>,
 
     /// Show a brief description before solving.
     #[arg(short, long)]
     verbose: bool,
+
+    /// Print how long set_input and each part took (plus allocations with --features count-allocs)
+    #[arg(long)]
+    time: bool,
+
+    /// Account from the config file to use for network requests
+    #[arg(long, global = true)]
+    account: Option<String>,
+
+    /// Threads for parallel solvers (default: config `threads`, else one per CPU); 1 runs sequentially
+    #[arg(long, global = true)]
+    threads: Option<usize>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Fetch solved puzzle pages and store their confirmed answers in input/answers.yaml
+    Harvest {
+        /// Harvest a single day. If omitted, harvests every implemented day.
+        #[arg(long)]
+        day: Option<u32>,
+    },
+
+    /// Re-run every day with stored answers against its cached input and report mismatches
+    Verify,
+
+    /// Run every registered variant of each day's parts and report where they disagree
+    Crosscheck {
+        /// Only check this day. If omitted, checks every day with variants.
+        #[arg(long)]
+        day: Option<u32>,
+
+        /// Check on generated input of this size instead of the cached input
+        #[arg(long)]
+        size: Option<usize>,
+
+        /// Number of generated inputs to check, with seeds counting up from the default seed
+        #[arg(long, default_value_t = 1, requires = "size")]
+        seeds: u64,
+    },
+
+    /// Download puzzle inputs into the cache in one throttled session
+    Fetch {
+        /// Fetch every unlocked day of the year that is not cached yet (re-run to resume)
+        #[arg(long, conflicts_with = "day", required_unless_present = "day")]
+        all: bool,
+
+        /// Fetch a single day
+        #[arg(long)]
+        day: Option<u32>,
+
+        /// Event year
+        #[arg(long, default_value_t = aocnet::YEAR)]
+        year: u32,
+    },
+
+    /// Show how long until a puzzle unlocks (the next locked day by default)
+    Countdown {
+        /// Day to count down to
+        #[arg(long)]
+        day: Option<u32>,
+    },
+
+    /// Time every day with the built-in timer, optionally saving or comparing a named baseline
+    Bench {
+        /// Only benchmark this day
+        #[arg(long)]
+        day: Option<u32>,
+
+        /// Save the results as baselines/<NAME>.json
+        #[arg(long, value_name = "NAME")]
+        save: Option<String>,
+
+        /// Compare against baselines/<NAME>.json and exit with status 1 on regressions
+        #[arg(long, value_name = "NAME")]
+        compare: Option<String>,
+
+        /// Slowdown in percent that counts as a regression
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+
+        /// Rewrite the README's "Benchmark Summary" table from these results
+        #[arg(long)]
+        update_readme: bool,
+
+        /// Measuring time per day in milliseconds
+        #[arg(long, default_value_t = 1000)]
+        budget_ms: u64,
+    },
+
+    /// Time a day on generated inputs of growing size and fit how its running time scales
+    Scale {
+        /// Day to sweep
+        #[arg(long)]
+        day: u32,
+
+        /// Smallest input size (defaults to 1/8 of the real-input size)
+        #[arg(long)]
+        from: Option<usize>,
+
+        /// Largest input size (defaults to 4x the real-input size)
+        #[arg(long)]
+        to: Option<usize>,
+
+        /// Ratio between consecutive sizes
+        #[arg(long, default_value_t = 2.0)]
+        factor: f64,
+
+        /// Write the measurements as CSV to this file
+        #[arg(long)]
+        csv: Option<PathBuf>,
+
+        /// Measuring time per size in milliseconds
+        #[arg(long, default_value_t = 500)]
+        budget_ms: u64,
+    },
+
+    /// Write a seeded synthetic input for a day (for benchmarking without the real input)
+    Generate {
+        /// Day to generate input for
+        #[arg(long)]
+        day: u32,
+
+        /// Input size in the day's natural unit (lines, ranges, points, ...); defaults to real-input size
+        #[arg(long)]
+        size: Option<usize>,
+
+        /// Random seed; the same seed and size always give the same input
+        #[arg(long, default_value_t = inputgen::DEFAULT_SEED)]
+        seed: u64,
+
+        /// File to write instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Show a private leaderboard (refreshed at most every 15 minutes)
+    Leaderboard {
+        /// Leaderboard ID (the number in its URL). Defaults to `leaderboard` from the config file.
+        #[arg(long)]
+        id: Option<String>,
+
+        /// Show per-member star times and part 1 -> part 2 deltas for one day
+        #[arg(long)]
+        day: Option<u32>,
+    },
+
+    /// Show your personal completion times, ranks and scores per day
+    Stats {
+        /// Event year (defaults to this crate's year)
+        #[arg(long)]
+        year: Option<u32>,
+
+        /// Print the stats as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check the config file and verify that the session token still works
+    #[command(alias = "whoami")]
+    Doctor {
+        /// Check every configured account instead of only the selected one
+        #[arg(long)]
+        all: bool,
+    },
 }
 
 #[derive(Debug, Deserialize)]
@@ -34,74 +250,114 @@ struct ProblemBrief {
     description: String,
 }
 
-// Takes a day number and returns the expected cached input path for that day.
-fn input_path(day: u32) -> PathBuf {
-    PathBuf::from("input").join(format!("day{:02}.txt", day))
+// Takes a warning or retry notice from the AoC client and prints it to stderr.
+fn print_notice(message: &str) {
+    eprintln!("{message}");
 }
 
-// Takes a cache path, reads it line-by-line, and returns the puzzle input lines.
-fn read_local_input(path: &PathBuf) -> io::Result<Vec<String>> {
-    let f = fs::File::open(path)?;
-    let br = BufReader::new(f);
-    let mut lines = Vec::new();
-    for line in br.lines() {
-        lines.push(line?);
-    }
-    Ok(lines)
-}
-
-// Takes a directory name, creates it if needed, and returns any filesystem error.
-fn ensure_dir(name: &str) -> io::Result<()> {
-    fs::create_dir_all(name)
+// Takes an optional account name and event year, resolves the session from the environment or config, and returns it with an AoC client.
+fn connect(account: Option<&str>, year: u32) -> io::Result<(aocnet::Client, Session)> {
+    let config = Config::load_default()?;
+    let session = config.session(account).map_err(io::Error::other)?;
+    let mut options = config.client_options();
+    options.year = year;
+    options.notify = print_notice;
+    let client = aocnet::Client::new(&session.token, options);
+    Ok((client, session))
 }
 
-// Takes fetched input lines, writes them to the day cache file, and returns any filesystem error.
-fn write_input_cache(day: u32, lines: &[String]) -> io::Result<()> {
-    ensure_dir("input")?;
-    let path = input_path(day);
-    let mut f = fs::File::create(path)?;
-    for l in lines {
-        writeln!(f, "{l}")?;
+// Takes a failure context and an error, and returns a message that adds the client's hint for network errors.
+fn describe_error(context: &str, e: &(dyn std::error::Error + 'static)) -> String {
+    match e.downcast_ref::<aocnet::Error>() {
+        Some(net) => format!("{context}: {net}\n  hint: {}", net.advice()),
+        None => format!("{context}: {e}"),
     }
-    Ok(())
 }
 
-// Takes a day and fetch flag, fetches online when requested, otherwise reads cache, and returns input lines.
-fn fetch_or_read_input(day: u32, force_fetch: bool) -> io::Result<Vec<String>> {
-    let online = force_fetch || std::env::var("AOC_ONLINE").ok().as_deref() == Some("1");
-    let session = std::env::var("AOC_SESSION").unwrap_or_default();
+// Takes a day and fetch flags, fetches online when requested and not cached, otherwise maps the cached file, and returns the input.
+fn fetch_or_read_input(
+    day: u32,
+    fetch: bool,
+    refetch: bool,
+    account: Option<&str>,
+) -> io::Result<Input> {
+    let online = fetch || refetch || std::env::var("AOC_ONLINE").ok().as_deref() == Some("1");
+    let path = cache::input_path(day);
+    let cached = path.exists();
+    let locked = aocnet::time_until_unlock(aocnet::YEAR, day, SystemTime::now());
 
-    if online {
-        if session.is_empty() {
-            eprintln!("AOC_ONLINE=1/--fetch but AOC_SESSION is not set. Falling back to cache.");
-        } else {
-            eprintln!("Fetching input for day {day}...");
-            match aocnet::fetch_input(day, &session) {
-                Ok(lines) => {
-                    if let Err(e) = write_input_cache(day, &lines) {
-                        eprintln!("Warning: failed to write cache: {e}");
+    if let (true, Some(remaining)) = (online, locked) {
+        eprintln!(
+            "Day {day} unlocks in {}; not fetching yet (use --wait to wait for it).",
+            aocnet::format_countdown(remaining)
+        );
+    } else if online && cached && !refetch {
+        if fetch {
+            eprintln!(
+                "Input for day {day} is already cached at {:?}; not downloading it again (use --refetch).",
+                path
+            );
+        }
+    } else if online {
+        match connect(account, aocnet::YEAR) {
+            Err(e) => eprintln!("AOC_ONLINE=1/--fetch but {e}. Falling back to cache."),
+            Ok((client, session)) => {
+                eprintln!("Fetching input for day {day}...");
+                match client.fetch_input(day) {
+                    Ok(lines) => {
+                        match cache::store_input(&path, &lines, &session.account, refetch) {
+                            Ok(stored) => {
+                                report_refetch(&stored, &lines);
+                                return Ok(Input::from_lines(&lines));
+                            }
+                            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                                eprintln!("Rejected fetched input for day {day}: {e}. Falling back to cache.");
+                            }
+                            Err(e) => {
+                                eprintln!("Warning: failed to write cache: {e}");
+                                return Ok(Input::from_lines(&lines));
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("{}", describe_error("Network fetch failed", &e));
+                        eprintln!("Falling back to cache.");
                     }
-                    return Ok(lines);
-                }
-                Err(e) => {
-                    eprintln!("Network fetch failed: {e}. Falling back to cache.");
                 }
             }
         }
     }
 
-    let path = input_path(day);
-    read_local_input(&path).map_err(|e| {
+    if let Some(warning) = cache::check_provenance(&path) {
+        eprintln!("Warning: {warning}");
+    }
+    Input::open(&path).map_err(|e| {
         io::Error::new(
             e.kind(),
             format!(
-                "Failed to read cached input at {:?}: {e}. (Tip: run with --fetch and set AOC_SESSION)",
+                "Failed to read cached input at {:?}: {e}. (Tip: run with --fetch and configure a session)",
                 path
             ),
         )
     })
 }
 
+// Takes the outcome of a refetch and the new lines, and prints where the backup went and how the input changed.
+fn report_refetch(stored: &cache::Stored, lines: &[String]) {
+    let (Some(backup), Some(previous)) = (&stored.backup, &stored.previous) else {
+        return;
+    };
+    eprintln!("Previous input kept at {}", backup.display());
+
+    let diff = cache::diff_lines(previous, lines, 20);
+    if diff.is_empty() {
+        eprintln!("Refetched input is identical to the previous copy.");
+    }
+    for line in diff {
+        eprintln!("{line}");
+    }
+}
+
 // Takes a day number, loads embedded YAML metadata, and prints its brief description if available.
 fn print_problem_brief(day: u32) -> io::Result<()> {
     let briefs: BTreeMap<u32, ProblemBrief> = serde_yaml::from_str(PROBLEMS_YAML).map_err(|e| {
@@ -120,37 +376,949 @@ fn print_problem_brief(day: u32) -> io::Result<()> {
     Ok(())
 }
 
-// Parses CLI arguments, loads input, optionally prints metadata, runs the requested solver, and reports results.
+// Takes a day, sleeps with a live countdown until its puzzle unlocks, and returns once it is available.
+fn wait_for_unlock(day: u32) {
+    // AoC's clock and ours rarely agree to the second; give the puzzle a moment to appear.
+    const GRACE: Duration = Duration::from_secs(2);
+
+    let mut waited = false;
+    while let Some(remaining) = aocnet::time_until_unlock(aocnet::YEAR, day, SystemTime::now()) {
+        waited = true;
+        eprint!(
+            "\rDay {day} unlocks in {}  ",
+            aocnet::format_countdown(remaining)
+        );
+        let _ = io::stderr().flush();
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+    if waited {
+        eprintln!("\rDay {day} is unlocked.               ");
+        thread::sleep(GRACE);
+    }
+}
+
+// Takes a day selection and year, downloads every missing unlocked day, prints a status report, and returns whether all succeeded.
+fn fetch_days(day: Option<u32>, year: u32, account: Option<&str>) -> io::Result<bool> {
+    let (client, session) = connect(account, year)?;
+    let days = match day {
+        Some(day) => vec![day],
+        None => (1..=aocnet::puzzle_count(year)).collect(),
+    };
+
+    let report = cache::fill(
+        &client,
+        &session.account,
+        Path::new("input"),
+        days,
+        |day, status| {
+            eprintln!("Day {day:>2}: {status}");
+        },
+    );
+
+    let count = |wanted: fn(&cache::FillStatus) -> bool| {
+        report.iter().filter(|(_, status)| wanted(status)).count()
+    };
+    let failed = count(|s| matches!(s, cache::FillStatus::Failed(_)));
+    let skipped = count(|s| matches!(s, cache::FillStatus::Skipped));
+    println!(
+        "{year}: {} fetched, {} already cached, {} locked, {failed} failed, {skipped} skipped",
+        count(|s| matches!(s, cache::FillStatus::Fetched(_))),
+        count(|s| matches!(s, cache::FillStatus::Cached)),
+        count(|s| matches!(s, cache::FillStatus::Locked(_))),
+    );
+    if failed + skipped > 0 {
+        println!("Re-run the same command to resume with the missing days.");
+    }
+
+    Ok(failed + skipped == 0)
+}
+
+// Takes an optional day, prints the countdown to that day (or the next locked one), and returns.
+fn print_countdown(day: Option<u32>) {
+    let now = SystemTime::now();
+    let last = aocnet::puzzle_count(aocnet::YEAR);
+    let day = day.or_else(|| {
+        (1..=last).find(|&day| aocnet::time_until_unlock(aocnet::YEAR, day, now).is_some())
+    });
+
+    match day.map(|day| (day, aocnet::time_until_unlock(aocnet::YEAR, day, now))) {
+        Some((day, Some(remaining))) => println!(
+            "Day {day} unlocks in {}",
+            aocnet::format_countdown(remaining)
+        ),
+        Some((day, None)) => println!("Day {day} is already unlocked."),
+        None => println!("All {last} puzzles of {} are unlocked.", aocnet::YEAR),
+    }
+}
+
+// Takes an optional day, fetches each solved puzzle page, and records its confirmed answers in the answer store.
+fn harvest_answers(day: Option<u32>, account: Option<&str>) -> io::Result<()> {
+    let (client, _) = connect(account, aocnet::YEAR)?;
+    let path = AnswerStore::default_path();
+    let mut store = AnswerStore::load(&path)?;
+    let days = day.map_or_else(days::implemented_days, |day| vec![day]);
+
+    for day in days {
+        let html = match client.fetch_puzzle_page(day) {
+            Ok(html) => html,
+            Err(aocnet::Error::Unauthorized) => {
+                store.save()?;
+                return Err(io::Error::other(describe_error(
+                    "Harvest stopped",
+                    &aocnet::Error::Unauthorized,
+                )));
+            }
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    describe_error(&format!("Day {day}: fetch failed"), &e)
+                );
+                continue;
+            }
+        };
+
+        let confirmed = aocnet::parse_confirmed_answers(&html);
+        if confirmed.is_empty() {
+            println!("Day {day}: no confirmed answers yet");
+            continue;
+        }
+
+        let changed = store.record(day, DayAnswers::from_confirmed(&confirmed));
+        println!(
+            "Day {day}: {} confirmed answer(s){}",
+            confirmed.len(),
+            if changed { " (updated)" } else { "" }
+        );
+    }
+
+    store.save()?;
+    println!("Saved answers to {}", path.display());
+    Ok(())
+}
+
+// Takes an optional account and an all-accounts flag, checks config and sessions, and returns whether all checks passed.
+fn doctor(account: Option<&str>, all: bool) -> io::Result<bool> {
+    match Config::default_path() {
+        Some(path) if path.exists() => println!("Config: {}", path.display()),
+        Some(path) => println!("Config: {} (not present)", path.display()),
+        None => println!("Config: no location (HOME is not set)"),
+    }
+    let config = Config::load_default()?;
+
+    let mut options = config.client_options();
+    options.notify = print_notice;
+    match &options.contact {
+        Some(contact) => println!("Contact: {contact}"),
+        None => println!("Contact: not configured (set `contact` or AOC_CONTACT)"),
+    }
+
+    let sessions = if all {
+        config
+            .account_names()
+            .into_iter()
+            .map(|name| config.session(Some(name)))
+            .collect()
+    } else {
+        vec![config.session(account)]
+    };
+
+    let mut healthy = true;
+    for session in sessions {
+        let session = match session {
+            Ok(session) => session,
+            Err(e) => {
+                healthy = false;
+                println!("Session: {e}");
+                continue;
+            }
+        };
+
+        let client = aocnet::Client::new(&session.token, options.clone());
+        match client.whoami() {
+            Ok(user) => {
+                let stars = user.stars.map(|n| format!(" ({n}*)")).unwrap_or_default();
+                println!("{}: logged in as {}{stars}", session.account, user.name);
+            }
+            Err(e) => {
+                healthy = false;
+                println!("{}", describe_error(&session.account, &e));
+            }
+        }
+    }
+
+    Ok(healthy)
+}
+
+// Takes an optional leaderboard ID and day, loads the leaderboard through its cache, and prints standings or one day.
+fn show_leaderboard(id: Option<String>, day: Option<u32>, account: Option<&str>) -> io::Result<()> {
+    let config = Config::load_default()?;
+    let Some(id) = id.or_else(|| config.leaderboard.clone()) else {
+        return Err(io::Error::other(
+            "no leaderboard ID given (use --id or set `leaderboard` in the config file)",
+        ));
+    };
+
+    let (client, _) = connect(account, aocnet::YEAR)?;
+    let board = leaderboard::load(&client, &id, &leaderboard::cache_path(&id)).map_err(|e| {
+        io::Error::other(describe_error(
+            &format!("Failed to load leaderboard {id}"),
+            e.as_ref(),
+        ))
+    })?;
+
+    match day {
+        Some(day) => print!("{}", board.render_day(day)),
+        None => print!("{}", board.render_standings()),
+    }
+    Ok(())
+}
+
+// What to do with the results of a `bench` run.
+struct BenchOptions {
+    save: Option<String>,
+    compare: Option<String>,
+    threshold: f64,
+    update_readme: bool,
+    budget: Duration,
+}
+
+// Takes an optional day and bench options, times each day, handles baselines and the README, and returns whether no day regressed.
+fn run_benchmarks(day: Option<u32>, options: &BenchOptions) -> io::Result<bool> {
+    if cfg!(debug_assertions) {
+        eprintln!("Warning: this is a debug build; use `cargo run --release -- bench` for meaningful timings.");
+    }
+    let before = options.compare.as_deref().map(Baseline::load).transpose()?;
+
+    let machine = MachineInfo::current();
+    println!(
+        "{} ({}/{}, {} threads), {}",
+        machine.cpu, machine.os, machine.arch, machine.threads, machine.rustc
+    );
+    let mut baseline = Baseline {
+        name: options
+            .save
+            .clone()
+            .unwrap_or_else(|| "unsaved".to_string()),
+        recorded_at: benchmark::now_secs(),
+        machine,
+        days: BTreeMap::new(),
+    };
+
+    println!(
+        "{:>3} {:>12} {:>12} {:>12} {:>12}  (µs, median)",
+        "Day", "set_input", "part1", "part2", "full"
+    );
+    let days = day.map_or_else(days::implemented_days, |day| vec![day]);
+    for day in days {
+        let Some((input, synthetic)) = benchmark::load_input(day) else {
+            println!("{day:>3}  no input");
+            continue;
+        };
+        let Some(mut timing) = benchmark::measure_day(day, &input, options.budget) else {
+            println!("{day:>3}  not implemented");
+            continue;
+        };
+        timing.synthetic = synthetic;
+        let part = |number| {
+            timing
+                .parts_us
+                .get(&number)
+                .map_or("-".to_string(), |&us| benchmark::format_micros(us))
+        };
+        let bonus: String = timing
+            .parts_us
+            .range(3..)
+            .map(|(number, &us)| format!("  part{number} {}", benchmark::format_micros(us)))
+            .collect();
+        println!(
+            "{day:>3} {:>12} {:>12} {:>12} {:>12}{bonus}{}",
+            benchmark::format_micros(timing.set_input_us),
+            part(1),
+            part(2),
+            benchmark::format_micros(timing.full_us),
+            if synthetic { "  (synthetic input)" } else { "" }
+        );
+        baseline.days.insert(day, timing);
+    }
+
+    if allocstats::is_active() {
+        println!("Allocations per stage (count, bytes, peak live bytes):");
+        for (day, timing) in &baseline.days {
+            let Some(allocs) = &timing.allocations else {
+                continue;
+            };
+            println!("{day:>3}  set_input: {}", allocs.set_input);
+            for (part, stats) in &allocs.parts {
+                println!("     part{part}:     {stats}");
+            }
+        }
+    }
+
+    if options.save.is_some() {
+        println!("Saved baseline to {}", baseline.save()?.display());
+    }
+
+    let mut ok = true;
+    if let Some(before) = &before {
+        println!(
+            "Compared with baseline {:?} (threshold {}%):",
+            before.name, options.threshold
+        );
+        if before.machine != baseline.machine {
+            println!(
+                "  note: recorded on a different machine or toolchain ({}, {})",
+                before.machine.cpu, before.machine.rustc
+            );
+        }
+        for change in benchmark::compare(before, &baseline, options.threshold) {
+            println!(
+                "  Day {:02}: {} -> {} µs ({:+.1}%){}",
+                change.day,
+                benchmark::format_micros(change.before_us),
+                benchmark::format_micros(change.after_us),
+                change.percent,
+                if change.regression {
+                    "  REGRESSION"
+                } else {
+                    ""
+                }
+            );
+            ok &= !change.regression;
+        }
+    }
+
+    if options.update_readme {
+        let readme = fs::read_to_string("README.md")?;
+        let updated =
+            benchmark::replace_readme_section(&readme, &benchmark::readme_section(&baseline));
+        cache::write_atomic(Path::new("README.md"), updated.as_bytes())?;
+        println!("Updated the benchmark summary in README.md");
+    }
+
+    Ok(ok)
+}
+
+// What a `scale` run sweeps over and where it writes its CSV.
+struct ScaleOptions {
+    from: Option<usize>,
+    to: Option<usize>,
+    factor: f64,
+    csv: Option<PathBuf>,
+    budget: Duration,
+}
+
+// Takes a day and scale options, times the day over a geometric series of generated sizes, prints the fitted growth exponents, and writes the CSV.
+fn run_scaling(day: u32, options: &ScaleOptions) -> io::Result<()> {
+    if cfg!(debug_assertions) {
+        eprintln!("Warning: this is a debug build; use `cargo run --release -- scale` for meaningful timings.");
+    }
+    let Some(default_size) = inputgen::default_size(day) else {
+        return Err(io::Error::other(format!(
+            "No input generator for day {day}"
+        )));
+    };
+    let from = options.from.unwrap_or((default_size / 8).max(1));
+    let to = options.to.unwrap_or(default_size * 4);
+    if options.factor <= 1.0 || from > to {
+        return Err(io::Error::other(
+            "Need --factor above 1 and --from no larger than --to",
+        ));
+    }
+    let sizes = benchmark::geometric_sizes(from, to, options.factor);
+    let parts: Vec<u8> = days::make_solver(day)
+        .map(|solver| solver.declared_parts())
+        .unwrap_or_default()
+        .iter()
+        .map(|part| part.number)
+        .collect();
+
+    println!(
+        "Day {day} on generated input, sizes {from}..={to} (x{}):",
+        options.factor
+    );
+    let part_columns: String = parts
+        .iter()
+        .map(|part| format!(" {:>12}", format!("part{part}")))
+        .collect();
+    println!(
+        "{:>9} {:>11} {:>12}{part_columns} {:>12}  (µs, median)",
+        "size", "bytes", "set_input", "full"
+    );
+    let points = benchmark::sweep(day, &sizes, options.budget, |point| {
+        let t = &point.timing;
+        let part_columns: String = t
+            .parts_us
+            .values()
+            .map(|&us| format!(" {:>12}", benchmark::format_micros(us)))
+            .collect();
+        println!(
+            "{:>9} {:>11} {:>12}{part_columns} {:>12}",
+            point.size,
+            benchmark::group_digits(point.bytes as u64),
+            benchmark::format_micros(t.set_input_us),
+            benchmark::format_micros(t.full_us)
+        );
+    });
+    if points.is_empty() {
+        return Err(io::Error::other(format!("Day {day} is not implemented")));
+    }
+
+    let exponent = |stage: &dyn Fn(&benchmark::DayTiming) -> f64| {
+        let pairs: Vec<(f64, f64)> = points
+            .iter()
+            .map(|point| (point.size as f64, stage(&point.timing)))
+            .collect();
+        benchmark::fit_exponent(&pairs).map_or("-".to_string(), |k| format!("{k:.2}"))
+    };
+    let part_exponents: String = parts
+        .iter()
+        .map(|&part| {
+            let k = exponent(&|t| t.parts_us.get(&part).copied().unwrap_or(0.0));
+            format!(", part{part} {k}")
+        })
+        .collect();
+    println!(
+        "Growth exponent k (time ∝ size^k): set_input {}{part_exponents}, full {}",
+        exponent(&|t| t.set_input_us),
+        exponent(&|t| t.full_us)
+    );
+
+    if let Some(path) = &options.csv {
+        cache::write_atomic(path, benchmark::scaling_csv(day, &points).as_bytes())?;
+        println!("Wrote {} rows to {}", points.len(), path.display());
+    }
+    Ok(())
+}
+
+// Takes a day, optional size, seed and output path, generates a synthetic input, and writes it out.
+fn generate_input(
+    day: u32,
+    size: Option<usize>,
+    seed: u64,
+    output: Option<&Path>,
+) -> io::Result<()> {
+    let size = size.or_else(|| inputgen::default_size(day));
+    // Line-by-line days are written as they are generated, so sizes beyond memory work.
+    if let (Some(size), true) = (size, days::streaming_days().contains(&day)) {
+        let mut out: Box<dyn Write> = match output {
+            Some(path) => Box::new(io::BufWriter::new(fs::File::create(path)?)),
+            None => Box::new(io::stdout().lock()),
+        };
+        if inputgen::write_streamed(day, size, seed, &mut out)? {
+            out.flush()?;
+            if let Some(path) = output {
+                eprintln!("Wrote size {size} input to {}", path.display());
+            }
+            return Ok(());
+        }
+    }
+    let Some(lines) = size.and_then(|size| inputgen::generate(day, size, seed)) else {
+        return Err(io::Error::other(format!(
+            "No input generator for day {day}"
+        )));
+    };
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    match output {
+        Some(path) => {
+            cache::write_atomic(path, text.as_bytes())?;
+            eprintln!("Wrote {} lines to {}", lines.len(), path.display());
+        }
+        None => io::stdout().write_all(text.as_bytes())?,
+    }
+    Ok(())
+}
+
+// Takes a year, output format and optional account, fetches the personal stats page, and prints it.
+fn show_stats(year: u32, json: bool, account: Option<&str>) -> io::Result<()> {
+    let (client, _) = connect(account, year)?;
+    let html = client
+        .fetch_personal_stats()
+        .map_err(|e| io::Error::other(describe_error("Failed to fetch personal stats", &e)))?;
+    let stats = PersonalStats::parse(year, &html);
+
+    if json {
+        println!("{}", stats.to_json());
+    } else {
+        print!("{}", stats.render());
+    }
+    Ok(())
+}
+
+// Runs every day with stored answers against its cached input, prints per-part results, and returns whether all matched.
+fn verify_answers() -> io::Result<bool> {
+    let store = AnswerStore::load(&AnswerStore::default_path())?;
+    let mut all_match = true;
+    let mut checked = 0;
+
+    for (day, expected) in store.iter() {
+        if !days::implemented_days().contains(&day) {
+            continue;
+        }
+        let input = match Input::open(&cache::input_path(day)) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("Day {day}: skipped ({e})");
+                continue;
+            }
+        };
+
+        let report = solve::solve_input(day, &input, &SolveOptions::default());
+        for error in &report.errors {
+            all_match = false;
+            println!("Day {day}: ERROR ({error})");
+        }
+        for part in [1, 2] {
+            let (Some(want), Some(got)) = (expected.part(part), report.answer(part)) else {
+                continue;
+            };
+
+            checked += 1;
+            if got == want {
+                println!("Day {day} Part {part}: ok ({got})");
+            } else {
+                all_match = false;
+                println!("Day {day} Part {part}: MISMATCH (got {got}, expected {want})");
+            }
+        }
+    }
+
+    println!("{checked} answer(s) checked");
+    Ok(all_match)
+}
+
+// Takes an optional day, generated-input size and seed count, runs every variant of each part that has variants,
+// prints each implementation's answer and time, and returns whether they all agreed.
+//
+// Without a size each day runs on its cached input, or on synthetic input of the real size when none is cached.
+fn crosscheck_variants(day: Option<u32>, size: Option<usize>, seeds: u64) -> io::Result<bool> {
+    let days: Vec<u32> = match day {
+        Some(day) => vec![day],
+        None => days::implemented_days()
+            .into_iter()
+            .filter(|&day| days::make_solver(day).is_some_and(|s| !s.variants().is_empty()))
+            .collect(),
+    };
+
+    let mut all_agree = true;
+    for day in days {
+        let inputs: Vec<(Input, String)> = match size {
+            Some(size) => (inputgen::DEFAULT_SEED..inputgen::DEFAULT_SEED + seeds)
+                .filter_map(|seed| {
+                    let lines = inputgen::generate(day, size, seed)?;
+                    Some((
+                        Input::from_lines(&lines),
+                        format!(" (size {size}, seed {seed})"),
+                    ))
+                })
+                .collect(),
+            None => benchmark::load_input(day)
+                .map(|(input, synthetic)| {
+                    let source = if synthetic { " (synthetic input)" } else { "" };
+                    (input, source.to_string())
+                })
+                .into_iter()
+                .collect(),
+        };
+        if inputs.is_empty() {
+            println!("Day {day}: skipped (no input)");
+            continue;
+        }
+
+        for (input, source) in inputs {
+            let checks = match solve::crosscheck(day, &input) {
+                Ok(checks) => checks,
+                Err(e) => {
+                    all_agree = false;
+                    println!("Day {day}{source}: ERROR ({e})");
+                    continue;
+                }
+            };
+            if checks.is_empty() {
+                println!("Day {day}: no variants registered");
+            }
+            for check in checks {
+                let verdict = if check.agrees() { "ok" } else { "MISMATCH" };
+                all_agree &= check.agrees();
+                println!("Day {day} Part {}{source}: {verdict}", check.part);
+                for answer in &check.answers {
+                    let value = match &answer.answer {
+                        Ok(value) => value.clone(),
+                        Err(message) => format!("panicked: {message}"),
+                    };
+                    println!(
+                        "    {:<20} {:<24} {:>12} µs",
+                        answer.variant,
+                        value,
+                        benchmark::format_micros(answer.elapsed.as_secs_f64() * 1e6)
+                    );
+                }
+            }
+        }
+    }
+    Ok(all_agree)
+}
+
+// Parses CLI arguments, dispatches subcommands, picks the day, loads input, optionally prints metadata, runs the requested solver, and reports results.
 fn main() -> io::Result<()> {
     let args = Args::parse();
 
-    let lines = fetch_or_read_input(args.day, args.fetch)?;
+    // The config file is only read for its thread setting when --threads is absent and the file exists.
+    let threads = args.threads.or_else(|| {
+        let path = Config::default_path().filter(|path| path.exists())?;
+        match Config::load(&path) {
+            Ok(config) => config.threads,
+            Err(e) => {
+                eprintln!("Warning: ignoring the config file's thread setting: {e}");
+                None
+            }
+        }
+    });
+    if let Some(threads) = threads {
+        parallel::set_threads(threads).map_err(io::Error::other)?;
+    }
 
-    let mut solver = match days::make_solver(args.day) {
+    match args.command {
+        Some(Command::Harvest { day }) => return harvest_answers(day, args.account.as_deref()),
+        Some(Command::Bench {
+            day,
+            save,
+            compare,
+            threshold,
+            update_readme,
+            budget_ms,
+        }) => {
+            let options = BenchOptions {
+                save,
+                compare,
+                threshold,
+                update_readme,
+                budget: Duration::from_millis(budget_ms),
+            };
+            if !run_benchmarks(day, &options)? {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Command::Verify) => {
+            if !verify_answers()? {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Command::Crosscheck { day, size, seeds }) => {
+            if !crosscheck_variants(day, size, seeds)? {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Command::Fetch { all: _, day, year }) => {
+            if !fetch_days(day, year, args.account.as_deref())? {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Command::Countdown { day }) => {
+            print_countdown(day);
+            return Ok(());
+        }
+        Some(Command::Scale {
+            day,
+            from,
+            to,
+            factor,
+            csv,
+            budget_ms,
+        }) => {
+            let options = ScaleOptions {
+                from,
+                to,
+                factor,
+                csv,
+                budget: Duration::from_millis(budget_ms),
+            };
+            return run_scaling(day, &options);
+        }
+        Some(Command::Generate {
+            day,
+            size,
+            seed,
+            output,
+        }) => return generate_input(day, size, seed, output.as_deref()),
+        Some(Command::Leaderboard { id, day }) => {
+            return show_leaderboard(id, day, args.account.as_deref())
+        }
+        Some(Command::Stats { year, json }) => {
+            return show_stats(year.unwrap_or(aocnet::YEAR), json, args.account.as_deref())
+        }
+        Some(Command::Doctor { all }) => {
+            if !doctor(args.account.as_deref(), all)? {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        None => {}
+    }
+
+    let day = match args
+        .day
+        .or_else(|| aocnet::current_puzzle_day(aocnet::YEAR, SystemTime::now()))
+    {
+        Some(day) => day,
+        None => Args::command()
+            .error(
+                clap::error::ErrorKind::MissingRequiredArgument,
+                "--day is required outside the event when no subcommand is given",
+            )
+            .exit(),
+    };
+    if args.day.is_none() {
+        eprintln!("No --day given; using today's puzzle (day {day}).");
+    }
+
+    if args.list_parts {
+        return list_parts(day);
+    }
+
+    if args.wait {
+        wait_for_unlock(day);
+    }
+
+    let input = if args.stream {
+        None
+    } else if let Some(path) = &args.input {
+        Some(Input::open(path)?)
+    } else {
+        Some(fetch_or_read_input(
+            day,
+            args.fetch || args.wait,
+            args.refetch,
+            args.account.as_deref(),
+        )?)
+    };
+
+    if args.dump_parsed {
+        let input = input.expect("--dump-parsed conflicts with --stream");
+        return match days::dump_parsed(day, input.as_str()) {
+            Some(Ok(json)) => match writeln!(io::stdout(), "{json}") {
+                Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e),
+                _ => Ok(()),
+            },
+            Some(Err(e)) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Day {day} input does not parse: {e}"),
+            )),
+            None => {
+                eprintln!("Day {} not implemented.", day);
+                Ok(())
+            }
+        };
+    }
+
+    let mut solver = match days::make_solver(day) {
         Some(s) => s,
         None => {
-            eprintln!("Day {} not implemented.", args.day);
+            eprintln!("Day {} not implemented.", day);
             return Ok(());
         }
     };
 
+    let declared = solver.declared_parts();
+    let selected = match &args.part {
+        None => declared,
+        Some(selector) => match declared.iter().find(|part| part.matches(selector)) {
+            Some(&part) => vec![part],
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "Day {day} has no part {selector:?}; it declares {}",
+                        describe_parts(&declared)
+                    ),
+                ))
+            }
+        },
+    };
+    let variants = solver.variants();
+    let has_variant = |part: &PartInfo, name: &str| {
+        name == days::DEFAULT_VARIANT
+            || variants
+                .iter()
+                .any(|variant| variant.part == part.number && variant.name == name)
+    };
+    let plan: Vec<(PartInfo, &str)> = match args.variant.as_deref() {
+        None => selected
+            .iter()
+            .map(|&part| (part, days::DEFAULT_VARIANT))
+            .collect(),
+        Some(name) if selected.iter().any(|part| has_variant(part, name)) => selected
+            .iter()
+            .map(|&part| {
+                let variant = if has_variant(&part, name) {
+                    name
+                } else {
+                    days::DEFAULT_VARIANT
+                };
+                (part, variant)
+            })
+            .collect(),
+        Some(name) => {
+            let names: Vec<String> = variants
+                .iter()
+                .map(|variant| format!("{} (part {})", variant.name, variant.part))
+                .collect();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Day {day} has no variant {name:?} of the selected parts; its variants: {}",
+                    if names.is_empty() {
+                        "none".to_string()
+                    } else {
+                        names.join(", ")
+                    }
+                ),
+            ));
+        }
+    };
+
     if args.verbose {
-        print_problem_brief(args.day)?;
+        print_problem_brief(day)?;
     }
 
-    solver.set_input(&lines);
-
-    match args.part {
-        Some(1) => println!("{}", solver.part1()),
-        Some(2) => println!("{}", solver.part2()),
-        Some(p) => eprintln!("Invalid --part {p}. Use 1 or 2."),
+    let mut timings = Vec::new();
+    match &input {
+        Some(input) => {
+            let (result, stage) = timed(|| days::set_input(solver.as_mut(), input));
+            result.map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Day {day} input is malformed: {e}"),
+                )
+            })?;
+            timings.push(("set_input".to_string(), stage));
+        }
         None => {
-            let p1 = solver.part1();
-            let p2 = solver.part2();
-            println!("Day {} Part 1: {}", args.day, p1);
-            println!("Day {} Part 2: {}", args.day, p2);
+            let path = args.input.unwrap_or_else(|| cache::input_path(day));
+            let file = fs::File::open(&path).map_err(|e| {
+                io::Error::new(e.kind(), format!("Failed to open {}: {e}", path.display()))
+            })?;
+            let mut reader = io::BufReader::with_capacity(1 << 16, file);
+            let (result, stage) = timed(|| solver.stream_input(&mut reader));
+            match result {
+                Err(e) if e.kind() == io::ErrorKind::Unsupported => {
+                    return Err(io::Error::other(format!(
+                        "Day {day} cannot stream its input; days that can: {:?}",
+                        days::streaming_days()
+                    )))
+                }
+                Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Day {day} input is malformed: {e}"),
+                    ))
+                }
+                result => result?,
+            }
+            timings.push(("stream_input".to_string(), stage));
+        }
+    }
+
+    let results: Vec<_> = match solver.parts() {
+        Some(shared) if args.parallel_parts && plan.len() > 1 => plan
+            .par_iter()
+            .map(|&(part, variant)| {
+                let stage = timed_concurrently(|| shared.solve_variant(part.number, variant));
+                (part, variant, stage)
+            })
+            .collect(),
+        _ => plan
+            .iter()
+            .map(|&(part, variant)| {
+                let stage = timed(|| solver.solve_variant(part.number, variant));
+                (part, variant, stage)
+            })
+            .collect(),
+    };
+    for (part, variant, (answer, stage)) in results {
+        let label = if variant == days::DEFAULT_VARIANT {
+            format!("part{}", part.number)
+        } else {
+            format!("part{} {variant}", part.number)
+        };
+        timings.push((label, stage));
+        if args.part.is_some() {
+            println!("{answer}");
+        } else if variant == days::DEFAULT_VARIANT {
+            println!("Day {} Part {}: {}", day, part.number, answer);
+        } else {
+            println!("Day {} Part {} ({variant}): {}", day, part.number, answer);
         }
     }
 
+    if args.time {
+        let width = timings
+            .iter()
+            .map(|(stage, _)| stage.len())
+            .fold(12, usize::max);
+        for (stage, (elapsed, allocs)) in timings {
+            let allocs = allocs
+                .map(|stats| format!("  ({stats})"))
+                .unwrap_or_default();
+            eprintln!(
+                "  {stage:<width$} {:>12} µs{allocs}",
+                benchmark::format_micros(elapsed.as_secs_f64() * 1e6)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+// Takes a day and prints the parts its solver declares, one per line, each followed by its registered variants.
+fn list_parts(day: u32) -> io::Result<()> {
+    let Some(solver) = days::make_solver(day) else {
+        eprintln!("Day {} not implemented.", day);
+        return Ok(());
+    };
+    let variants = solver.variants();
+    for part in solver.declared_parts() {
+        println!("{:>2}  {:<20} {}", part.number, part.name, part.description);
+        for variant in variants
+            .iter()
+            .filter(|variant| variant.part == part.number)
+        {
+            println!("    {:<20} variant: {}", variant.name, variant.description);
+        }
+    }
     Ok(())
 }
+
+// Takes declared parts and returns them listed for an error message, e.g. "1 (zero-stops) and 2 (zero-passes)".
+fn describe_parts(parts: &[PartInfo]) -> String {
+    let listed: Vec<String> = parts
+        .iter()
+        .map(|part| format!("{} ({})", part.number, part.name))
+        .collect();
+    match listed.split_last() {
+        Some((last, [])) => format!("only {last}"),
+        Some((last, rest)) => format!("{} and {last}", rest.join(", ")),
+        None => "no parts".to_string(),
+    }
+}
+
+// Takes a solver stage that runs alongside another, and returns its result with the elapsed time.
+//
+// Allocations are not reported: the counters are global, so they would include the other stage.
+fn timed_concurrently<T>(stage: impl FnOnce() -> T) -> (T, (Duration, Option<AllocStats>)) {
+    let started = Instant::now();
+    let result = stage();
+    (result, (started.elapsed(), None))
+}
+
+// Takes a solver stage, runs it, and returns its result with the elapsed time and allocations, if counted.
+fn timed<T>(stage: impl FnOnce() -> T) -> (T, (Duration, Option<AllocStats>)) {
+    let started = Instant::now();
+    let (result, allocs) = allocstats::measure(stage);
+    (result, (started.elapsed(), allocs))
+}
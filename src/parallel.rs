@@ -0,0 +1,61 @@
+use rayon::ThreadPoolBuilder;
+
+// Sizing of rayon's global pool, which the parallel solvers (days 9, 10 and 12) run on.
+//
+// Without a call to `set_threads` rayon picks one thread per CPU (or RAYON_NUM_THREADS).
+
+// Takes a thread count and returns a pool builder for it; a single thread is the calling thread itself.
+fn pool_builder(threads: usize) -> ThreadPoolBuilder {
+    let builder = ThreadPoolBuilder::new().num_threads(threads);
+    if threads == 1 {
+        builder.use_current_thread()
+    } else {
+        builder
+    }
+}
+
+// Takes a thread count and sizes the global pool before any solver runs.
+//
+// With 1 every parallel iterator runs sequentially on the calling thread, which keeps timings
+// comparable with single-threaded implementations. Must be called from the thread that runs the
+// solvers, and at most once per process.
+pub fn set_threads(threads: usize) -> Result<(), String> {
+    if threads == 0 {
+        return Err("the thread count must be at least 1".to_string());
+    }
+    pool_builder(threads)
+        .build_global()
+        .map_err(|e| format!("failed to size the thread pool: {e}"))
+}
+
+// Returns how many threads the parallel solvers use.
+pub fn current_threads() -> usize {
+    rayon::current_num_threads()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rayon::prelude::*;
+    use std::collections::HashSet;
+    use std::thread;
+
+    #[test]
+    fn one_thread_runs_on_the_caller() {
+        let pool = pool_builder(1).build().unwrap();
+        let caller = thread::current().id();
+        let ids: HashSet<_> = pool.install(|| {
+            (0..1000)
+                .into_par_iter()
+                .map(|_| thread::current().id())
+                .collect()
+        });
+        assert_eq!(ids, HashSet::from([caller]));
+        assert_eq!(pool.current_num_threads(), 1);
+    }
+
+    #[test]
+    fn rejects_zero_threads() {
+        assert!(set_threads(0).is_err());
+    }
+}
@@ -0,0 +1,487 @@
+// Byte-level parsers shared by the solvers.
+//
+// They work on `&[u8]` straight out of `Input` and avoid UTF-8 and `str::parse` overhead. Integers
+// are read eight digits at a time with SWAR (SIMD within a register) arithmetic, and digit grids
+// are converted in one flat pass that the compiler vectorizes.
+
+use crate::input;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+const ZEROS: u64 = 0x3030_3030_3030_3030;
+
+// Takes eight bytes as a little-endian word and returns whether all of them are ASCII digits.
+#[inline]
+fn is_eight_digits(word: u64) -> bool {
+    // Digits are 0x30..=0x39: the high nibble must be 3 and adding 6 must not carry into it.
+    let high = word & 0xF0F0_F0F0_F0F0_F0F0;
+    let carried = (word.wrapping_add(0x0606_0606_0606_0606) & 0xF0F0_F0F0_F0F0_F0F0) >> 4;
+    high | carried == 0x3333_3333_3333_3333
+}
+
+// Takes eight ASCII digits as a little-endian word and returns their decimal value.
+#[inline]
+fn eight_digits(word: u64) -> u64 {
+    let word = word - ZEROS;
+    let word = (word.wrapping_mul(10) + (word >> 8)) & 0x00FF_00FF_00FF_00FF;
+    let word = (word.wrapping_mul(100) + (word >> 16)) & 0x0000_FFFF_0000_FFFF;
+    (word.wrapping_mul(10_000) + (word >> 32)) & 0xFFFF_FFFF
+}
+
+// Takes bytes and a byte to look for, and returns the index of its first occurrence.
+//
+// Compares eight bytes per step, which beats both a byte loop on short lines and `memchr`'s setup cost.
+pub fn find_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    const LOW: u64 = 0x0101_0101_0101_0101;
+    const HIGH: u64 = 0x8080_8080_8080_8080;
+
+    let pattern = LOW * u64::from(needle);
+    let mut chunks = haystack.chunks_exact(8);
+    let mut offset = 0;
+    for chunk in &mut chunks {
+        // Bytes equal to the needle become zero; the lowest set high bit marks the first of them.
+        let word = u64::from_le_bytes(chunk.try_into().unwrap()) ^ pattern;
+        let zeros = word.wrapping_sub(LOW) & !word & HIGH;
+        if zeros != 0 {
+            return Some(offset + zeros.trailing_zeros() as usize / 8);
+        }
+        offset += 8;
+    }
+    chunks
+        .remainder()
+        .iter()
+        .position(|&byte| byte == needle)
+        .map(|index| offset + index)
+}
+
+// Takes bytes and the set of bytes they may contain, and returns the first byte outside that set.
+//
+// Checks 64 bytes per step with branch-free comparisons the compiler vectorizes, so validating a
+// whole grid costs about as much as copying it.
+pub fn find_other_byte<const N: usize>(bytes: &[u8], allowed: [u8; N]) -> Option<u8> {
+    let is_allowed = |byte: u8| allowed.iter().fold(false, |hit, &a| hit | (a == byte));
+    bytes
+        .chunks(64)
+        .find(|chunk| !chunk.iter().fold(true, |ok, &byte| ok & is_allowed(byte)))
+        .and_then(|chunk| chunk.iter().copied().find(|&byte| !is_allowed(byte)))
+}
+
+// Takes ASCII decimal digits and returns their value, or None if empty, not all digits, or too large.
+#[inline]
+pub fn parse_u64(bytes: &[u8]) -> Option<u64> {
+    // Up to 19 digits cannot overflow; longer numbers take the checked path.
+    if bytes.is_empty() || bytes.len() > 19 {
+        return parse_u64_checked(bytes);
+    }
+
+    let mut value = 0u64;
+    let mut chunks = bytes.chunks_exact(8);
+    for chunk in &mut chunks {
+        let word = u64::from_le_bytes(chunk.try_into().unwrap());
+        if !is_eight_digits(word) {
+            return None;
+        }
+        value = value * 100_000_000 + eight_digits(word);
+    }
+    for &byte in chunks.remainder() {
+        let digit = byte.wrapping_sub(b'0');
+        if digit > 9 {
+            return None;
+        }
+        value = value * 10 + u64::from(digit);
+    }
+    Some(value)
+}
+
+// Takes ASCII decimal digits and returns their value with overflow checks, digit by digit.
+fn parse_u64_checked(bytes: &[u8]) -> Option<u64> {
+    if bytes.is_empty() {
+        return None;
+    }
+    bytes.iter().try_fold(0u64, |value, &byte| {
+        let digit = byte.wrapping_sub(b'0');
+        if digit > 9 {
+            return None;
+        }
+        value.checked_mul(10)?.checked_add(u64::from(digit))
+    })
+}
+
+// Takes decimal digits with an optional leading '-' or '+' and returns the signed value.
+#[inline]
+pub fn parse_i64(bytes: &[u8]) -> Option<i64> {
+    match bytes.split_first()? {
+        (b'-', digits) => {
+            let magnitude = parse_u64(digits)?;
+            if magnitude == i64::MIN.unsigned_abs() {
+                Some(i64::MIN)
+            } else {
+                i64::try_from(magnitude).ok().map(|value| -value)
+            }
+        }
+        (b'+', digits) => i64::try_from(parse_u64(digits)?).ok(),
+        _ => i64::try_from(parse_u64(bytes)?).ok(),
+    }
+}
+
+// Takes bytes and a separator and returns the fields between separators, with surrounding spaces trimmed.
+pub fn fields(bytes: &[u8], separator: u8) -> impl Iterator<Item = &[u8]> {
+    bytes
+        .split(move |&byte| byte == separator)
+        .map(<[u8]>::trim_ascii)
+}
+
+// Takes a separated list such as "162,817,812" and returns exactly N unsigned integers.
+pub fn parse_u64_array<const N: usize>(bytes: &[u8], separator: u8) -> Option<[u64; N]> {
+    let mut values = [0; N];
+    let mut fields = fields(bytes, separator);
+    for value in &mut values {
+        *value = parse_u64(fields.next()?)?;
+    }
+    fields.next().is_none().then_some(values)
+}
+
+// Takes a separated list such as "3,-1,4" and returns exactly N signed integers.
+pub fn parse_i64_array<const N: usize>(bytes: &[u8], separator: u8) -> Option<[i64; N]> {
+    let mut values = [0; N];
+    let mut fields = fields(bytes, separator);
+    for value in &mut values {
+        *value = parse_i64(fields.next()?)?;
+    }
+    fields.next().is_none().then_some(values)
+}
+
+// Takes a separated list of any length and returns its unsigned integers, skipping empty fields.
+pub fn parse_u64_list(bytes: &[u8], separator: u8) -> Option<Vec<u64>> {
+    fields(bytes, separator)
+        .filter(|field| !field.is_empty())
+        .map(parse_u64)
+        .collect()
+}
+
+// Takes a dash-separated range such as "10-14" and returns its (start, end) bounds.
+pub fn parse_range(bytes: &[u8]) -> Option<(u64, u64)> {
+    let [start, end] = parse_u64_array(bytes, b'-')?;
+    Some((start, end))
+}
+
+// Equal-width rows of bytes stored back to back.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Grid {
+    pub cells: Vec<u8>,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl Grid {
+    // Takes lines and returns them as a grid, or None if their widths differ. Blank lines are skipped.
+    pub fn parse<'a>(lines: impl IntoIterator<Item = &'a [u8]>) -> Option<Self> {
+        let mut grid = Self::default();
+        for line in lines {
+            if line.is_empty() {
+                continue;
+            }
+            if grid.rows == 0 {
+                grid.cols = line.len();
+            } else if line.len() != grid.cols {
+                return None;
+            }
+            grid.cells.extend_from_slice(line);
+            grid.rows += 1;
+        }
+        Some(grid)
+    }
+
+    // Takes lines of ASCII digits and returns a grid of digit values 0..=9, or None on other bytes or ragged rows.
+    pub fn parse_digits<'a>(lines: impl IntoIterator<Item = &'a [u8]>) -> Option<Self> {
+        let mut grid = Self::parse(lines)?;
+        // Two branch-free passes over the flat buffer, which the compiler vectorizes.
+        grid.cells
+            .iter_mut()
+            .for_each(|byte| *byte = byte.wrapping_sub(b'0'));
+        let largest = grid
+            .cells
+            .iter()
+            .fold(0, |largest, &digit| largest.max(digit));
+        (largest <= 9).then_some(grid)
+    }
+
+    // Takes lines and returns them as a grid, padding shorter rows with `fill`. Blank lines are skipped.
+    pub fn parse_padded<'a>(lines: impl IntoIterator<Item = &'a [u8]>, fill: u8) -> Self {
+        let mut text = Vec::new();
+        let mut widths = Vec::new();
+        for line in lines {
+            if !line.is_empty() {
+                text.extend_from_slice(line);
+                widths.push(line.len());
+            }
+        }
+        let rows = widths.len();
+        let cols = widths.iter().copied().max().unwrap_or(0);
+        if text.len() == rows * cols {
+            return Self {
+                cells: text,
+                rows,
+                cols,
+            };
+        }
+
+        let mut cells = Vec::with_capacity(rows * cols);
+        let mut start = 0;
+        for width in widths {
+            cells.extend_from_slice(&text[start..start + width]);
+            cells.resize(cells.len() + cols - width, fill);
+            start += width;
+        }
+        Self { cells, rows, cols }
+    }
+
+    // Takes a row index and returns that row.
+    pub fn row(&self, row: usize) -> &[u8] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    // Returns the rows in order.
+    pub fn row_iter(&self) -> impl Iterator<Item = &[u8]> {
+        self.cells.chunks_exact(self.cols.max(1)).take(self.rows)
+    }
+
+    // Takes a formatter and a byte-to-char mapping, and writes the grid one row per line.
+    pub fn write_rows(&self, f: &mut fmt::Formatter<'_>, cell: impl Fn(u8) -> char) -> fmt::Result {
+        for row in self.row_iter() {
+            let line: String = row.iter().map(|&byte| cell(byte)).collect();
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+
+    // Takes a serializer and writes the grid as one string per row, for grids of ASCII characters.
+    //
+    // Used with `#[serde(serialize_with = "Grid::serialize_text")]`; the plain `Serialize` writes
+    // each row as an array of numbers instead.
+    pub fn serialize_text<S: Serializer>(grid: &Grid, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(grid.row_iter().map(String::from_utf8_lossy))
+    }
+}
+
+impl Serialize for Grid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut grid = serializer.serialize_struct("Grid", 3)?;
+        grid.serialize_field("rows", &self.rows)?;
+        grid.serialize_field("cols", &self.cols)?;
+        grid.serialize_field("cells", &self.row_iter().collect::<Vec<_>>())?;
+        grid.end()
+    }
+}
+
+// Why puzzle input could not be parsed into a day's input model.
+//
+// Boxed so that per-line results such as `Result<Point3, ParseError>` stay small on the happy path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError(Box<ParseErrorDetail>);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ParseErrorDetail {
+    line: Option<usize>,
+    message: String,
+}
+
+impl ParseError {
+    // Takes a description of the problem and returns an error not tied to a line.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self(Box::new(ParseErrorDetail {
+            line: None,
+            message: message.into(),
+        }))
+    }
+
+    // Takes a description of the expected format and the offending text, and returns an error quoting it.
+    //
+    // Kept out of line so the per-line parsers that call it stay small.
+    #[cold]
+    #[inline(never)]
+    pub fn invalid(expected: &str, text: &[u8]) -> Self {
+        Self::new(format!("{expected}: {:?}", String::from_utf8_lossy(text)))
+    }
+
+    // Takes a 1-based line number and returns the error located at that line.
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.0.line = Some(line);
+        self
+    }
+
+    // Returns the 1-based line number, when the problem is confined to one line.
+    pub fn line(&self) -> Option<usize> {
+        self.0.line
+    }
+
+    // Returns the description of the problem without its line number.
+    pub fn message(&self) -> &str {
+        &self.0.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line() {
+            Some(line) => write!(f, "line {line}: {}", self.message()),
+            None => f.write_str(self.message()),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Puzzle input with one item per line, such as day 8's junction coordinates.
+//
+// Parsing skips blank lines and reports the line number of the first item that fails; displaying
+// writes one item per line.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct Lines<T>(pub Vec<T>);
+
+impl<T: FromStr<Err = ParseError>> FromStr for Lines<T> {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, ParseError> {
+        let mut items = Vec::new();
+        for (index, line) in input::lines(text).enumerate() {
+            let line = line.trim_ascii();
+            if !line.is_empty() {
+                items.push(line.parse().map_err(|e: ParseError| e.at_line(index + 1))?);
+            }
+        }
+        Ok(Self(items))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Lines<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for item in &self.0 {
+            writeln!(f, "{item}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_unsigned_integers_of_every_length() {
+        let mut value = 0u64;
+        for length in 1..=20 {
+            value = value * 10 + (length % 10);
+            assert_eq!(
+                parse_u64(value.to_string().as_bytes()),
+                Some(value),
+                "{value}"
+            );
+        }
+        assert_eq!(parse_u64(b"18446744073709551615"), Some(u64::MAX));
+        assert_eq!(parse_u64(b"18446744073709551616"), None);
+        assert_eq!(parse_u64(b"000000000000042"), Some(42));
+        for bad in [&b""[..], b"12a4", b"1234567/", b"12345678:", b" 1", b"-1"] {
+            assert_eq!(parse_u64(bad), None, "{:?}", String::from_utf8_lossy(bad));
+        }
+    }
+
+    #[test]
+    fn finds_bytes_in_every_position() {
+        let text = b"abcdefghijklmnopqrstuvwxyz\n";
+        for (index, &byte) in text.iter().enumerate() {
+            assert_eq!(find_byte(text, byte), Some(index));
+            assert_eq!(find_byte(&text[index + 1..], byte), None);
+        }
+        assert_eq!(find_byte(b"\x80\x01\n", b'\n'), Some(2));
+        assert_eq!(find_byte(b"", b'\n'), None);
+    }
+
+    #[test]
+    fn finds_bytes_outside_an_allowed_set() {
+        let mut map = vec![b'.'; 200];
+        assert_eq!(find_other_byte(&map, [b'.', b'@']), None);
+        map[150] = b'#';
+        map[170] = b'x';
+        assert_eq!(find_other_byte(&map, [b'.', b'@']), Some(b'#'));
+        assert_eq!(find_other_byte(b"", [b'.']), None);
+    }
+
+    #[test]
+    fn parses_signed_integers() {
+        assert_eq!(parse_i64(b"-68"), Some(-68));
+        assert_eq!(parse_i64(b"+48"), Some(48));
+        assert_eq!(parse_i64(b"9223372036854775807"), Some(i64::MAX));
+        assert_eq!(parse_i64(b"-9223372036854775808"), Some(i64::MIN));
+        assert_eq!(parse_i64(b"9223372036854775808"), None);
+        assert_eq!(parse_i64(b"-"), None);
+    }
+
+    #[test]
+    fn parses_separated_lists() {
+        assert_eq!(
+            parse_u64_array::<3>(b"162,817,812", b','),
+            Some([162, 817, 812])
+        );
+        assert_eq!(parse_u64_array::<3>(b"162,817", b','), None);
+        assert_eq!(parse_u64_array::<2>(b"1,2,3", b','), None);
+        assert_eq!(parse_i64_array::<2>(b"3, -1", b','), Some([3, -1]));
+        assert_eq!(parse_range(b"10-14"), Some((10, 14)));
+        assert_eq!(parse_u64_list(b"11-22,95-115,", b','), None);
+        assert_eq!(parse_u64_list(b"1,2,,3,", b','), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn parses_fixed_width_grids() {
+        let lines = [&b"987"[..], b"811", b"", b"234"];
+        let grid = Grid::parse_digits(lines).unwrap();
+        assert_eq!((grid.rows, grid.cols), (3, 3));
+        assert_eq!(grid.row(1), &[8, 1, 1]);
+        assert_eq!(grid.row_iter().last(), Some(&[2, 3, 4][..]));
+
+        assert_eq!(Grid::parse_digits([&b"12"[..], b"1x"]), None);
+        assert_eq!(Grid::parse([&b"..@"[..], b".@"]), None);
+
+        let padded = Grid::parse_padded([&b"12 "[..], b" 4"], b' ');
+        assert_eq!((padded.rows, padded.cols), (2, 3));
+        assert_eq!(padded.row(1), b" 4 ");
+
+        let json = serde_json::to_value(&grid).unwrap();
+        assert_eq!(json["cells"][0], serde_json::json!([9, 8, 7]));
+        let text = Grid::serialize_text(&padded, serde_json::value::Serializer).unwrap();
+        assert_eq!(text, serde_json::json!(["12 ", " 4 "]));
+    }
+
+    #[test]
+    fn parses_and_displays_one_item_per_line() {
+        let numbers: Lines<Number> = "1\n\n 22 \n".parse().unwrap();
+        assert_eq!(numbers, Lines(vec![Number(1), Number(22)]));
+        assert_eq!(numbers.to_string(), "1\n22\n");
+
+        let error = "1\n2\nx\n".parse::<Lines<Number>>().unwrap_err();
+        assert_eq!(error.line(), Some(3));
+        assert_eq!(error.to_string(), "line 3: not a number: \"x\"");
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Number(u64);
+
+    impl FromStr for Number {
+        type Err = ParseError;
+
+        fn from_str(text: &str) -> Result<Self, ParseError> {
+            parse_u64(text.as_bytes())
+                .map(Number)
+                .ok_or_else(|| ParseError::new(format!("not a number: {text:?}")))
+        }
+    }
+
+    impl fmt::Display for Number {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+}
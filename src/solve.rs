@@ -0,0 +1,363 @@
+use crate::days;
+use crate::input::Input;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+// Library entry point: solve a day from its input text without the CLI's plumbing.
+//
+//     let report = aoc2025::solve(1, "L68\nL30\n", &aoc2025::SolveOptions::default());
+//     println!("{:?} in {:?}", report.answer(1), report.timings.parts.get(&1));
+
+// Which parts `solve` runs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SolveOptions {
+    // Only this part; None runs every part the day declares.
+    pub part: Option<u8>,
+    // Run the parts at once on rayon's pool; each part's time is then measured while the others run.
+    pub parallel_parts: bool,
+}
+
+// Why a day or part produced no answer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub enum SolveError {
+    UnknownDay(u32),
+    InvalidPart(u8),
+    // The input does not have the shape the day declares, e.g. a blank line inside a grid.
+    MalformedInput(String),
+    // The solver panicked in this stage, usually on malformed input.
+    Panicked { stage: String, message: String },
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownDay(day) => write!(f, "day {day} is not implemented"),
+            Self::InvalidPart(part) => write!(f, "this day declares no part {part}"),
+            Self::MalformedInput(message) => write!(f, "malformed input: {message}"),
+            Self::Panicked { stage, message } => write!(f, "{stage} panicked: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+// Wall-clock time of each stage that ran.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SolveTimings {
+    pub set_input: Duration,
+    // Keyed by part number.
+    pub parts: BTreeMap<u8, Duration>,
+}
+
+// Everything one `solve` call produced: the answers that could be computed, timings and errors.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SolveReport {
+    pub day: u32,
+    // Keyed by part number.
+    pub answers: BTreeMap<u8, String>,
+    pub timings: SolveTimings,
+    pub errors: Vec<SolveError>,
+}
+
+impl SolveReport {
+    // Returns whether every requested stage finished without error.
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    // Takes a part number and returns its answer, if that part ran successfully.
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        self.answers.get(&part).map(String::as_str)
+    }
+}
+
+// Takes a day, its puzzle input text and options, runs the solver, and returns the answers with timings and errors.
+pub fn solve(day: u32, input: &str, options: &SolveOptions) -> SolveReport {
+    solve_input(day, &Input::from(input), options)
+}
+
+// Takes a day, an already loaded input and options, and returns the same report as `solve`.
+//
+// The input is normalized and checked against the day's declared shape first (see `days::set_input`).
+// Solver panics are caught and reported as errors; the default panic hook still prints them.
+pub fn solve_input(day: u32, input: &Input, options: &SolveOptions) -> SolveReport {
+    let mut report = SolveReport {
+        day,
+        ..SolveReport::default()
+    };
+    let Some(mut solver) = days::make_solver(day) else {
+        report.errors.push(SolveError::UnknownDay(day));
+        return report;
+    };
+    let declared: Vec<u8> = solver
+        .declared_parts()
+        .iter()
+        .map(|part| part.number)
+        .collect();
+    let parts = match options.part {
+        None => declared,
+        Some(part) if declared.contains(&part) => vec![part],
+        Some(part) => {
+            report.errors.push(SolveError::InvalidPart(part));
+            return report;
+        }
+    };
+
+    let (result, elapsed) = run_stage(|| days::set_input(solver.as_mut(), input));
+    report.timings.set_input = elapsed;
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => {
+            report
+                .errors
+                .push(SolveError::MalformedInput(e.to_string()));
+            return report;
+        }
+        Err(message) => {
+            report.errors.push(SolveError::Panicked {
+                stage: "set_input".to_string(),
+                message,
+            });
+            return report;
+        }
+    }
+
+    let results: Vec<_> = match solver.parts() {
+        Some(shared) if options.parallel_parts && parts.len() > 1 => parts
+            .par_iter()
+            .map(|&part| (part, run_stage(|| shared.solve_part(part))))
+            .collect(),
+        _ => parts
+            .iter()
+            .map(|&part| (part, run_stage(|| solver.solve_part(part))))
+            .collect(),
+    };
+
+    for (part, (result, elapsed)) in results {
+        report.timings.parts.insert(part, elapsed);
+        match result {
+            Ok(value) => {
+                report.answers.insert(part, value);
+            }
+            Err(message) => report.errors.push(SolveError::Panicked {
+                stage: format!("part{part}"),
+                message,
+            }),
+        }
+    }
+    report
+}
+
+// One implementation's answer to a part in a `crosscheck`, or its panic message.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct VariantAnswer {
+    pub variant: String,
+    pub answer: Result<String, String>,
+    pub elapsed: Duration,
+}
+
+// Every implementation's answer to one part: the default first, then its variants.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct VariantCheck {
+    pub part: u8,
+    pub answers: Vec<VariantAnswer>,
+}
+
+impl VariantCheck {
+    // Returns whether every implementation finished and gave the same answer.
+    pub fn agrees(&self) -> bool {
+        let first = self.answers.first().map(|answer| &answer.answer);
+        self.answers
+            .iter()
+            .all(|answer| answer.answer.is_ok() && Some(&answer.answer) == first)
+    }
+}
+
+// Takes a day and its input, runs the default and every registered variant of each part that has variants,
+// and returns their answers per part, or why the day could not run.
+pub fn crosscheck(day: u32, input: &Input) -> Result<Vec<VariantCheck>, SolveError> {
+    let mut solver = days::make_solver(day).ok_or(SolveError::UnknownDay(day))?;
+    let variants = solver.variants();
+    match run_stage(|| days::set_input(solver.as_mut(), input)).0 {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => return Err(SolveError::MalformedInput(e.to_string())),
+        Err(message) => {
+            return Err(SolveError::Panicked {
+                stage: "set_input".to_string(),
+                message,
+            })
+        }
+    }
+
+    let mut checks = Vec::new();
+    for part in solver.declared_parts() {
+        let names: Vec<&str> = variants
+            .iter()
+            .filter(|variant| variant.part == part.number)
+            .map(|variant| variant.name)
+            .collect();
+        if names.is_empty() {
+            continue;
+        }
+        let answers = std::iter::once(days::DEFAULT_VARIANT)
+            .chain(names)
+            .map(|variant| {
+                let (answer, elapsed) = run_stage(|| solver.solve_variant(part.number, variant));
+                VariantAnswer {
+                    variant: variant.to_string(),
+                    answer,
+                    elapsed,
+                }
+            })
+            .collect();
+        checks.push(VariantCheck {
+            part: part.number,
+            answers,
+        });
+    }
+    Ok(checks)
+}
+
+// Takes a stage, runs it while catching panics, and returns its result or panic message with the elapsed time.
+fn run_stage<T>(stage: impl FnOnce() -> T) -> (Result<T, String>, Duration) {
+    let started = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(stage)).map_err(panic_message);
+    (result, started.elapsed())
+}
+
+// Takes a panic payload and returns its message.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY01: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+
+    #[test]
+    fn solves_both_parts_from_text() {
+        let report = solve(1, DAY01, &SolveOptions::default());
+        assert!(report.is_ok(), "{:?}", report.errors);
+        assert_eq!((report.answer(1), report.answer(2)), (Some("3"), Some("6")));
+        assert_eq!(report.timings.parts.len(), 2);
+
+        let only_two = solve(
+            1,
+            DAY01,
+            &SolveOptions {
+                part: Some(2),
+                ..SolveOptions::default()
+            },
+        );
+        assert_eq!((only_two.answer(1), only_two.answer(2)), (None, Some("6")));
+        assert_eq!(only_two.timings.parts.keys().collect::<Vec<_>>(), vec![&2]);
+    }
+
+    #[test]
+    fn runs_both_parts_in_parallel_with_the_same_answers() {
+        let parallel = SolveOptions {
+            parallel_parts: true,
+            ..SolveOptions::default()
+        };
+        for day in [1, 9] {
+            let text = crate::inputgen::generate(day, 50, crate::inputgen::DEFAULT_SEED).unwrap();
+            let input = Input::from_lines(&text);
+            let report = solve_input(day, &input, &parallel);
+            assert!(report.is_ok(), "{:?}", report.errors);
+            assert_eq!(report.timings.parts.len(), 2);
+            let sequential = solve_input(day, &input, &SolveOptions::default());
+            assert_eq!(report.answers, sequential.answers);
+        }
+    }
+
+    #[test]
+    fn reports_errors_instead_of_failing() {
+        let unknown = solve(25, "", &SolveOptions::default());
+        assert_eq!(unknown.errors, vec![SolveError::UnknownDay(25)]);
+
+        let part = solve(
+            1,
+            DAY01,
+            &SolveOptions {
+                part: Some(3),
+                ..SolveOptions::default()
+            },
+        );
+        assert_eq!(part.errors, vec![SolveError::InvalidPart(3)]);
+
+        let crlf = solve(1, &DAY01.replace('\n', "\r\n"), &SolveOptions::default());
+        assert_eq!(crlf.answer(2), Some("6"));
+        let blank = solve(1, "L68\n\nL30\n", &SolveOptions::default());
+        assert_eq!(
+            blank.errors,
+            vec![SolveError::MalformedInput(
+                "line 2: unexpected blank line".to_string()
+            )]
+        );
+
+        let malformed = solve(1, "Lxx\n", &SolveOptions::default());
+        assert_eq!(malformed.answer(1), None);
+        assert_eq!(
+            malformed.errors,
+            vec![SolveError::MalformedInput(
+                "line 1: rotation clicks must be a number up to 2147483547: \"Lxx\"".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn runs_only_the_parts_a_day_declares() {
+        let text = crate::inputgen::generate(12, 4, crate::inputgen::DEFAULT_SEED).unwrap();
+        let input = Input::from_lines(&text);
+        let report = solve_input(12, &input, &SolveOptions::default());
+        assert!(report.is_ok(), "{:?}", report.errors);
+        assert_eq!(report.answers.keys().collect::<Vec<_>>(), vec![&1]);
+
+        let second = solve_input(
+            12,
+            &input,
+            &SolveOptions {
+                part: Some(2),
+                ..SolveOptions::default()
+            },
+        );
+        assert_eq!(second.errors, vec![SolveError::InvalidPart(2)]);
+    }
+
+    #[test]
+    fn crosscheck_compares_every_variant() {
+        let checks = crosscheck(1, &Input::from(DAY01)).unwrap();
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].part, 2);
+        assert!(checks[0].agrees(), "{checks:?}");
+        let variants: Vec<&str> = checks[0]
+            .answers
+            .iter()
+            .map(|answer| answer.variant.as_str())
+            .collect();
+        assert_eq!(variants, vec!["default", "click-simulation"]);
+
+        let mut disagreeing = checks[0].clone();
+        disagreeing.answers[1].answer = Ok("7".to_string());
+        assert!(!disagreeing.agrees());
+
+        assert!(crosscheck(2, &Input::from("11-22\n")).unwrap().is_empty());
+        assert_eq!(
+            crosscheck(25, &Input::from("")),
+            Err(SolveError::UnknownDay(25))
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use serde::Serialize;
+use std::fmt::Write as _;
+
+// One part's row cells from the personal stats page.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PartStats {
+    // Completion time after unlock, as shown ("00:12:34" or ">24h").
+    pub time: String,
+    // The same time in seconds; None when AoC only says ">24h".
+    pub seconds: Option<u64>,
+    pub rank: Option<u32>,
+    pub score: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DayStats {
+    pub day: u32,
+    pub part1: Option<PartStats>,
+    pub part2: Option<PartStats>,
+}
+
+// The account's personal leaderboard statistics for one event, in day order.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct PersonalStats {
+    pub year: u32,
+    pub days: Vec<DayStats>,
+}
+
+impl PersonalStats {
+    // Takes the event year and the /{year}/leaderboard/self page HTML and returns the parsed stats.
+    pub fn parse(year: u32, html: &str) -> Self {
+        let text = strip_tags(html);
+        let mut days: Vec<DayStats> = text.lines().filter_map(parse_row).collect();
+        days.sort_by_key(|stats| stats.day);
+        Self { year, days }
+    }
+
+    // Returns the stats as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    // Renders the per-day table with totals and returns its text.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "Personal stats {} ({} days with stars)",
+            self.year,
+            self.days.len()
+        );
+        let _ = writeln!(
+            out,
+            "{:>3}  {:>10} {:>6} {:>5}  {:>10} {:>6} {:>5}",
+            "Day", "Part 1", "Rank", "Score", "Part 2", "Rank", "Score"
+        );
+
+        let cells = |part: &Option<PartStats>| -> String {
+            let number = |value: Option<u32>| value.map_or("-".to_string(), |v| v.to_string());
+            match part {
+                Some(part) => format!(
+                    "{:>10} {:>6} {:>5}",
+                    part.time,
+                    number(part.rank),
+                    number(part.score)
+                ),
+                None => format!("{:>10} {:>6} {:>5}", "-", "-", "-"),
+            }
+        };
+        for stats in &self.days {
+            let _ = writeln!(
+                out,
+                "{:>3}  {}  {}",
+                stats.day,
+                cells(&stats.part1),
+                cells(&stats.part2)
+            );
+        }
+
+        let stars: usize = self
+            .days
+            .iter()
+            .map(|stats| stats.part1.is_some() as usize + stats.part2.is_some() as usize)
+            .sum();
+        let score: u32 = self
+            .days
+            .iter()
+            .flat_map(|stats| [&stats.part1, &stats.part2])
+            .flatten()
+            .filter_map(|part| part.score)
+            .sum();
+        let _ = writeln!(out, "{stars} stars, {score} points");
+        out
+    }
+}
+
+// Takes one line of the stats table and returns the day's stats, or None for headers and prose.
+//
+// Rows are "day" followed by the same columns for each part: time, then rank and score when the
+// event has a global leaderboard. Unsolved parts show "-" in every column.
+fn parse_row(line: &str) -> Option<DayStats> {
+    let mut tokens = line.split_whitespace();
+    let day: u32 = tokens.next()?.parse().ok()?;
+    let cells: Vec<&str> = tokens.collect();
+    let width = match cells.len() {
+        2 | 4 | 6 => cells.len() / 2,
+        _ => return None,
+    };
+    if !looks_like_time(cells[0]) || !looks_like_time(cells[width]) {
+        return None;
+    }
+
+    let part = |cells: &[&str]| -> Option<PartStats> {
+        if cells[0] == "-" {
+            return None;
+        }
+        let number = |index: usize| cells.get(index).and_then(|cell| cell.parse().ok());
+        Some(PartStats {
+            time: cells[0].to_string(),
+            seconds: parse_time(cells[0]),
+            rank: number(1),
+            score: number(2),
+        })
+    };
+
+    Some(DayStats {
+        day,
+        part1: part(&cells[..width]),
+        part2: part(&cells[width..]),
+    })
+}
+
+// Takes a table cell and returns whether it can be a time column ("-", ">24h" or HH:MM:SS).
+fn looks_like_time(cell: &str) -> bool {
+    cell == "-" || cell == ">24h" || parse_time(cell).is_some()
+}
+
+// Takes an HH:MM:SS cell and returns the time in seconds.
+fn parse_time(cell: &str) -> Option<u64> {
+    let mut fields = cell.split(':').map(|field| field.parse::<u64>().ok());
+    let (hours, minutes, seconds) = (fields.next()??, fields.next()??, fields.next()??);
+    if fields.next().is_some() {
+        return None;
+    }
+    Some(hours * 3600 + minutes * 60 + seconds)
+}
+
+// Takes HTML and returns its text with tags removed and basic entities decoded.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&gt;", ">")
+        .replace("&lt;", "<")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article><p>These are your personal leaderboard statistics.</p>
+<pre>      <span class="leaderboard-daydesc-first">--------Part 1--------</span>   <span class="leaderboard-daydesc-both">--------Part 2--------</span>
+Day   <span class="leaderboard-daydesc-first">    Time   Rank  Score</span>   <span class="leaderboard-daydesc-both">    Time   Rank  Score</span>
+  3   00:12:34   1234      0   00:20:05   1500      0
+  2       &gt;24h  20000      0          -      -      -
+  1   00:04:10     87     14   00:06:00    120      0
+</pre>
+</article>
+</main>"#;
+
+    #[test]
+    fn parses_rows_for_both_parts() {
+        let stats = PersonalStats::parse(2024, PAGE);
+        assert_eq!(
+            stats.days.iter().map(|d| d.day).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+
+        let day1 = &stats.days[0];
+        assert_eq!(
+            day1.part1,
+            Some(PartStats {
+                time: "00:04:10".to_string(),
+                seconds: Some(250),
+                rank: Some(87),
+                score: Some(14),
+            })
+        );
+
+        let day2 = &stats.days[1];
+        assert_eq!(day2.part1.as_ref().unwrap().time, ">24h");
+        assert_eq!(day2.part1.as_ref().unwrap().seconds, None);
+        assert_eq!(day2.part2, None);
+    }
+
+    #[test]
+    fn parses_time_only_rows() {
+        let stats = PersonalStats::parse(2025, "<pre>  5   01:02:03   -\n</pre>");
+        assert_eq!(stats.days[0].part1.as_ref().unwrap().seconds, Some(3723));
+        assert_eq!(stats.days[0].part1.as_ref().unwrap().rank, None);
+        assert_eq!(stats.days[0].part2, None);
+    }
+
+    #[test]
+    fn fetches_from_the_fake_site() {
+        use crate::aocnet::{Client, ClientOptions, Error};
+        use crate::fakeaoc::{FakeAoc, Fixtures};
+        use std::time::Duration;
+
+        let fixtures = Fixtures {
+            stats: Some(PAGE.to_string()),
+            ..Fixtures::default()
+        };
+        let site = FakeAoc::start(fixtures).unwrap();
+        let dir = std::env::temp_dir().join(format!("aoc2025-stats-{}", std::process::id()));
+        let client = |session: &str| {
+            let options = ClientOptions {
+                contact: Some("tests".to_string()),
+                min_interval: Duration::ZERO,
+                request_log: dir.join("requests.log"),
+                base_url: site.base_url(),
+                ..ClientOptions::default()
+            };
+            Client::new(session, options)
+        };
+
+        let html = client("fake-session").fetch_personal_stats().unwrap();
+        assert_eq!(PersonalStats::parse(2025, &html).days.len(), 3);
+        assert!(matches!(
+            client("stale").fetch_personal_stats(),
+            Err(Error::Unauthorized)
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn renders_table_and_json() {
+        let stats = PersonalStats::parse(2024, PAGE);
+        let table = stats.render();
+        assert!(table.contains("  1    00:04:10     87    14    00:06:00    120     0"));
+        assert!(table.ends_with("5 stars, 14 points\n"));
+
+        let json: serde_json::Value = serde_json::from_str(&stats.to_json()).unwrap();
+        assert_eq!(json["days"][0]["part1"]["rank"], 87);
+        assert_eq!(json["days"][1]["part2"], serde_json::Value::Null);
+    }
+}